@@ -1,29 +1,30 @@
 use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
 
 use crate::error::Error;
+use crate::storage_contract::StorageContract;
 use crate::types::DataKey;
 use crate::ChainLogisticsContractClient;
 
 // ─── Storage helpers ─────────────────────────────────────────────────────────
 
 fn get_admin(env: &Env) -> Option<Address> {
-    env.storage().persistent().get(&DataKey::Admin)
+    StorageContract::read_instance(env, &DataKey::Admin)
 }
 
 fn set_admin(env: &Env, admin: &Address) {
-    env.storage().persistent().set(&DataKey::Admin, admin);
+    StorageContract::write_instance(env, &DataKey::Admin, admin);
 }
 
 fn has_admin(env: &Env) -> bool {
-    env.storage().persistent().has(&DataKey::Admin)
+    env.storage().instance().has(&DataKey::Admin)
 }
 
 fn get_main_contract(env: &Env) -> Option<Address> {
-    env.storage().persistent().get(&DataKey::MainContract)
+    StorageContract::read_instance(env, &DataKey::MainContract)
 }
 
 fn set_main_contract(env: &Env, address: &Address) {
-    env.storage().persistent().set(&DataKey::MainContract, address);
+    StorageContract::write_instance(env, &DataKey::MainContract, address);
 }
 
 // ─── Internal helpers ────────────────────────────────────────────────────────
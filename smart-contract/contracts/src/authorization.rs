@@ -1,5 +1,6 @@
 use soroban_sdk::{contract, contractimpl, Address, Env, String, contracttype};
 use crate::error::Error;
+use crate::storage_contract::StorageContract;
 
 #[contracttype]
 #[derive(Clone)]
@@ -15,11 +16,9 @@ pub struct AuthorizationContract;
 #[contractimpl]
 impl AuthorizationContract {
     pub fn configure_initializer(env: Env, initializer: Address) -> Result<(), Error> {
-        match env.storage().persistent().get::<AuthDataKey, Address>(&AuthDataKey::Initializer) {
+        match StorageContract::read_instance::<AuthDataKey, Address>(&env, &AuthDataKey::Initializer) {
             None => {
-                env.storage()
-                    .persistent()
-                    .set(&AuthDataKey::Initializer, &initializer);
+                StorageContract::write_instance(&env, &AuthDataKey::Initializer, &initializer);
                 Ok(())
             }
             Some(existing) if existing == initializer => Ok(()),
@@ -35,10 +34,7 @@ impl AuthorizationContract {
         product_id: String,
         owner: Address,
     ) -> Result<(), Error> {
-        let initializer: Address = env
-            .storage()
-            .persistent()
-            .get(&AuthDataKey::Initializer)
+        let initializer: Address = StorageContract::read_instance(&env, &AuthDataKey::Initializer)
             .ok_or(Error::NotInitialized)?;
         caller.require_auth();
         if caller != initializer {
@@ -48,20 +44,20 @@ impl AuthorizationContract {
         if env.storage().persistent().has(&AuthDataKey::Owner(product_id.clone())) {
             return Err(Error::ProductAlreadyExists);
         }
-        env.storage().persistent().set(&AuthDataKey::Owner(product_id), &owner);
+        StorageContract::write_persistent(&env, &AuthDataKey::Owner(product_id), &owner);
         Ok(())
     }
 
     /// Update product ownership (transfer).
     pub fn update_product_owner(env: Env, old_owner: Address, product_id: String, new_owner: Address) -> Result<(), Error> {
         old_owner.require_auth();
-        let owner: Address = env.storage().persistent().get(&AuthDataKey::Owner(product_id.clone())).ok_or(Error::ProductNotFound)?;
+        let owner: Address = StorageContract::read_persistent(&env, &AuthDataKey::Owner(product_id.clone())).ok_or(Error::ProductNotFound)?;
         
         if owner != old_owner {
             return Err(Error::Unauthorized);
         }
 
-        env.storage().persistent().set(&AuthDataKey::Owner(product_id), &new_owner);
+        StorageContract::write_persistent(&env, &AuthDataKey::Owner(product_id), &new_owner);
         Ok(())
     }
 
@@ -74,12 +70,12 @@ impl AuthorizationContract {
     ) -> Result<(), Error> {
         owner.require_auth();
         
-        let current_owner: Address = env.storage().persistent().get(&AuthDataKey::Owner(product_id.clone())).ok_or(Error::ProductNotFound)?;
+        let current_owner: Address = StorageContract::read_persistent(&env, &AuthDataKey::Owner(product_id.clone())).ok_or(Error::ProductNotFound)?;
         if current_owner != owner {
             return Err(Error::Unauthorized);
         }
 
-        env.storage().persistent().set(&AuthDataKey::Authorized(product_id, actor), &true);
+        StorageContract::write_persistent(&env, &AuthDataKey::Authorized(product_id, actor), &true);
         Ok(())
     }
 
@@ -92,7 +88,7 @@ impl AuthorizationContract {
     ) -> Result<(), Error> {
         owner.require_auth();
 
-        let current_owner: Address = env.storage().persistent().get(&AuthDataKey::Owner(product_id.clone())).ok_or(Error::ProductNotFound)?;
+        let current_owner: Address = StorageContract::read_persistent(&env, &AuthDataKey::Owner(product_id.clone())).ok_or(Error::ProductNotFound)?;
         if current_owner != owner {
             return Err(Error::Unauthorized);
         }
@@ -103,13 +99,13 @@ impl AuthorizationContract {
 
     /// Check whether an actor is authorized.
     pub fn is_authorized(env: Env, product_id: String, actor: Address) -> Result<bool, Error> {
-        let owner: Address = env.storage().persistent().get(&AuthDataKey::Owner(product_id.clone())).ok_or(Error::ProductNotFound)?;
+        let owner: Address = StorageContract::read_persistent(&env, &AuthDataKey::Owner(product_id.clone())).ok_or(Error::ProductNotFound)?;
         
         if owner == actor {
             return Ok(true);
         }
 
-        Ok(env.storage().persistent().get(&AuthDataKey::Authorized(product_id, actor)).unwrap_or(false))
+        Ok(StorageContract::read_persistent(&env, &AuthDataKey::Authorized(product_id, actor)).unwrap_or(false))
     }
 }
 
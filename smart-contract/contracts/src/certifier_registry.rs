@@ -0,0 +1,399 @@
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol};
+
+use crate::error::CertifierError;
+use crate::storage_contract::StorageContract;
+use crate::types::Accreditation;
+
+#[contracttype]
+#[derive(Clone)]
+enum CertifierDataKey {
+    Admin,
+    Accreditation(Address, Symbol, u32),
+    AccreditationCount(Address, Symbol),
+}
+
+// ─── Storage helpers ─────────────────────────────────────────────────────────
+
+fn get_admin(env: &Env) -> Option<Address> {
+    StorageContract::read_instance(env, &CertifierDataKey::Admin)
+}
+
+fn set_admin(env: &Env, admin: &Address) {
+    StorageContract::write_instance(env, &CertifierDataKey::Admin, admin);
+}
+
+fn get_accreditation_count(env: &Env, certifier: &Address, standard: &Symbol) -> u32 {
+    StorageContract::read_persistent(
+        env,
+        &CertifierDataKey::AccreditationCount(certifier.clone(), standard.clone()),
+    )
+    .unwrap_or(0)
+}
+
+fn get_accreditation_at(
+    env: &Env,
+    certifier: &Address,
+    standard: &Symbol,
+    index: u32,
+) -> Option<Accreditation> {
+    StorageContract::read_persistent(
+        env,
+        &CertifierDataKey::Accreditation(certifier.clone(), standard.clone(), index),
+    )
+}
+
+/// Store the period at `index`, appending it if `index` is the current
+/// count.
+fn put_accreditation(env: &Env, index: u32, accreditation: &Accreditation) {
+    let certifier = &accreditation.certifier;
+    let standard = &accreditation.standard;
+    StorageContract::write_persistent(
+        env,
+        &CertifierDataKey::Accreditation(certifier.clone(), standard.clone(), index),
+        accreditation,
+    );
+    if index == get_accreditation_count(env, certifier, standard) {
+        StorageContract::write_persistent(
+            env,
+            &CertifierDataKey::AccreditationCount(certifier.clone(), standard.clone()),
+            &(index + 1),
+        );
+    }
+}
+
+// ─── Internal helpers ────────────────────────────────────────────────────────
+
+fn require_admin(env: &Env, caller: &Address) -> Result<(), CertifierError> {
+    let admin = get_admin(env).ok_or(CertifierError::NotInitialized)?;
+    caller.require_auth();
+    if &admin != caller {
+        return Err(CertifierError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// The index and contents of a certifier's current period for a standard.
+fn read_accreditation(
+    env: &Env,
+    certifier: &Address,
+    standard: &Symbol,
+) -> Result<(u32, Accreditation), CertifierError> {
+    match get_accreditation_count(env, certifier, standard) {
+        0 => Err(CertifierError::AccreditationNotFound),
+        count => get_accreditation_at(env, certifier, standard, count - 1)
+            .map(|accreditation| (count - 1, accreditation))
+            .ok_or(CertifierError::AccreditationNotFound),
+    }
+}
+
+/// Whether `at_time` falls in a period's window before any suspension.
+fn covers(accreditation: &Accreditation, at_time: u64) -> bool {
+    at_time >= accreditation.valid_from
+        && at_time < accreditation.valid_until
+        && accreditation.suspended_at.is_none_or(|suspended_at| at_time < suspended_at)
+}
+
+// ─── Contract ────────────────────────────────────────────────────────────────
+
+#[contract]
+pub struct CertifierRegistryContract;
+
+#[contractimpl]
+impl CertifierRegistryContract {
+    /// Initialize the contract with an admin address.
+    /// Can only be called once.
+    pub fn certifier_init(env: Env, admin: Address) -> Result<(), CertifierError> {
+        if get_admin(&env).is_some() {
+            return Err(CertifierError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        set_admin(&env, &admin);
+
+        env.events().publish(
+            (Symbol::new(&env, "certifier_registry_initialized"),),
+            admin,
+        );
+
+        Ok(())
+    }
+
+    /// Get the current admin address.
+    pub fn get_certifier_admin(env: Env) -> Result<Address, CertifierError> {
+        get_admin(&env).ok_or(CertifierError::NotInitialized)
+    }
+
+    /// Transfer admin privileges to a new address.
+    /// Requires authentication from both current and new admin.
+    pub fn transfer_certifier_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), CertifierError> {
+        require_admin(&env, &current_admin)?;
+        new_admin.require_auth();
+        set_admin(&env, &new_admin);
+
+        env.events().publish(
+            (Symbol::new(&env, "certifier_admin_transferred"),),
+            (current_admin, new_admin),
+        );
+
+        Ok(())
+    }
+
+    /// Accredit a certifier for a standard over `[valid_from, valid_until)`.
+    ///
+    /// Only the admin can accredit. Accrediting again ends the previous
+    /// period now and starts a new one that counts from now at the
+    /// earliest, so checks for earlier times are unaffected.
+    pub fn accredit_certifier(
+        env: Env,
+        admin: Address,
+        certifier: Address,
+        standard: Symbol,
+        valid_from: u64,
+        valid_until: u64,
+    ) -> Result<(), CertifierError> {
+        require_admin(&env, &admin)?;
+        if valid_until <= valid_from {
+            return Err(CertifierError::InvalidAccreditationWindow);
+        }
+
+        let mut index = 0;
+        let mut starts_at = valid_from;
+        if let Ok((current, mut previous)) = read_accreditation(&env, &certifier, &standard) {
+            let now = env.ledger().timestamp();
+            previous.valid_until = previous.valid_until.min(now);
+            put_accreditation(&env, current, &previous);
+            index = current + 1;
+            starts_at = valid_from.max(now);
+        }
+
+        let accreditation = Accreditation {
+            certifier: certifier.clone(),
+            standard: standard.clone(),
+            valid_from: starts_at,
+            valid_until,
+            suspended_at: None,
+        };
+        put_accreditation(&env, index, &accreditation);
+
+        env.events().publish(
+            (Symbol::new(&env, "certifier_accredited"), certifier, standard),
+            (valid_from, valid_until),
+        );
+
+        Ok(())
+    }
+
+    /// Suspend a certifier's accreditation for a standard from now on.
+    /// Checks for earlier times are unaffected. Only the admin can suspend.
+    pub fn suspend_certifier(
+        env: Env,
+        admin: Address,
+        certifier: Address,
+        standard: Symbol,
+    ) -> Result<(), CertifierError> {
+        require_admin(&env, &admin)?;
+        let (index, mut accreditation) = read_accreditation(&env, &certifier, &standard)?;
+        if accreditation.suspended_at.is_some() {
+            return Err(CertifierError::CertifierAlreadySuspended);
+        }
+
+        let now = env.ledger().timestamp();
+        accreditation.suspended_at = Some(now);
+        put_accreditation(&env, index, &accreditation);
+
+        env.events().publish(
+            (Symbol::new(&env, "certifier_suspended"), certifier, standard),
+            now,
+        );
+
+        Ok(())
+    }
+
+    /// Lift a suspension from now on, for the rest of the suspended
+    /// period's window. The suspension stays on record, so checks for
+    /// times it covered are unaffected. Only the admin can reinstate.
+    pub fn reinstate_certifier(
+        env: Env,
+        admin: Address,
+        certifier: Address,
+        standard: Symbol,
+    ) -> Result<(), CertifierError> {
+        require_admin(&env, &admin)?;
+        let (index, suspended) = read_accreditation(&env, &certifier, &standard)?;
+        if suspended.suspended_at.is_none() {
+            return Err(CertifierError::CertifierNotSuspended);
+        }
+
+        let accreditation = Accreditation {
+            valid_from: suspended.valid_from.max(env.ledger().timestamp()),
+            suspended_at: None,
+            ..suspended
+        };
+        put_accreditation(&env, index + 1, &accreditation);
+
+        env.events().publish(
+            (Symbol::new(&env, "certifier_reinstated"), certifier, standard),
+            admin,
+        );
+
+        Ok(())
+    }
+
+    /// Get a certifier's current accreditation period for a standard.
+    pub fn get_accreditation(
+        env: Env,
+        certifier: Address,
+        standard: Symbol,
+    ) -> Result<Accreditation, CertifierError> {
+        read_accreditation(&env, &certifier, &standard).map(|(_, accreditation)| accreditation)
+    }
+
+    /// Check whether a certifier was accredited for a standard at a given
+    /// time: inside the window of the period in force then and not
+    /// suspended. Periods never overlap, so only the latest one starting
+    /// at or before `at_time` can cover it.
+    pub fn is_accredited(env: Env, certifier: Address, standard: Symbol, at_time: u64) -> bool {
+        for index in (0..get_accreditation_count(&env, &certifier, &standard)).rev() {
+            if let Some(accreditation) = get_accreditation_at(&env, &certifier, &standard, index) {
+                if accreditation.valid_from <= at_time {
+                    return covers(&accreditation, at_time);
+                }
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod test_certifier_registry {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger};
+
+    fn setup(env: &Env) -> (CertifierRegistryContractClient<'_>, Address) {
+        let contract_id = env.register_contract(None, CertifierRegistryContract);
+        let client = CertifierRegistryContractClient::new(env, &contract_id);
+        let admin = Address::generate(env);
+        client.certifier_init(&admin);
+        (client, admin)
+    }
+
+    #[test]
+    fn test_init() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, admin) = setup(&env);
+        assert_eq!(client.get_certifier_admin(), admin);
+
+        let res = client.try_certifier_init(&admin);
+        assert_eq!(res, Err(Ok(CertifierError::AlreadyInitialized)));
+    }
+
+    #[test]
+    fn test_accreditation_window() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, admin) = setup(&env);
+        let certifier = Address::generate(&env);
+        let organic = Symbol::new(&env, "organic");
+
+        assert!(!client.is_accredited(&certifier, &organic, &150));
+
+        client.accredit_certifier(&admin, &certifier, &organic, &100, &200);
+        assert!(!client.is_accredited(&certifier, &organic, &99));
+        assert!(client.is_accredited(&certifier, &organic, &100));
+        assert!(client.is_accredited(&certifier, &organic, &199));
+        assert!(!client.is_accredited(&certifier, &organic, &200));
+
+        // Accreditation is per standard
+        assert!(!client.is_accredited(&certifier, &Symbol::new(&env, "fairtrade"), &150));
+
+        let res = client.try_accredit_certifier(&admin, &certifier, &organic, &200, &200);
+        assert_eq!(res, Err(Ok(CertifierError::InvalidAccreditationWindow)));
+    }
+
+    #[test]
+    fn test_suspend_and_reinstate() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|l| l.timestamp = 500);
+
+        let (client, admin) = setup(&env);
+        let certifier = Address::generate(&env);
+        let organic = Symbol::new(&env, "organic");
+        client.accredit_certifier(&admin, &certifier, &organic, &0, &1_000);
+
+        client.suspend_certifier(&admin, &certifier, &organic);
+        assert!(client.is_accredited(&certifier, &organic, &499));
+        assert!(!client.is_accredited(&certifier, &organic, &500));
+        assert_eq!(client.get_accreditation(&certifier, &organic).suspended_at, Some(500));
+
+        env.ledger().with_mut(|l| l.timestamp = 800);
+        client.reinstate_certifier(&admin, &certifier, &organic);
+        assert!(client.is_accredited(&certifier, &organic, &800));
+        assert!(client.is_accredited(&certifier, &organic, &999));
+        assert!(!client.is_accredited(&certifier, &organic, &1_000));
+        // The lifted suspension still covers the times it was in force
+        assert!(client.is_accredited(&certifier, &organic, &499));
+        assert!(!client.is_accredited(&certifier, &organic, &500));
+        assert!(!client.is_accredited(&certifier, &organic, &600));
+        assert_eq!(client.get_accreditation(&certifier, &organic).valid_from, 800);
+        assert_eq!(client.get_accreditation(&certifier, &organic).suspended_at, None);
+
+        let res = client.try_reinstate_certifier(&admin, &certifier, &organic);
+        assert_eq!(res, Err(Ok(CertifierError::CertifierNotSuspended)));
+
+        let res = client.try_suspend_certifier(&admin, &Address::generate(&env), &organic);
+        assert_eq!(res, Err(Ok(CertifierError::AccreditationNotFound)));
+    }
+
+    #[test]
+    fn test_accrediting_again_keeps_earlier_periods() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, admin) = setup(&env);
+        let certifier = Address::generate(&env);
+        let organic = Symbol::new(&env, "organic");
+        client.accredit_certifier(&admin, &certifier, &organic, &100, &200);
+
+        env.ledger().with_mut(|l| l.timestamp = 300);
+        client.accredit_certifier(&admin, &certifier, &organic, &0, &1_000);
+        assert!(client.is_accredited(&certifier, &organic, &150));
+        assert!(!client.is_accredited(&certifier, &organic, &50));
+        assert!(!client.is_accredited(&certifier, &organic, &250));
+        assert!(client.is_accredited(&certifier, &organic, &300));
+        assert_eq!(client.get_accreditation(&certifier, &organic).valid_from, 300);
+
+        // A new window cuts the current one short from now on
+        env.ledger().with_mut(|l| l.timestamp = 600);
+        client.accredit_certifier(&admin, &certifier, &organic, &700, &800);
+        assert!(client.is_accredited(&certifier, &organic, &599));
+        assert!(!client.is_accredited(&certifier, &organic, &650));
+        assert!(client.is_accredited(&certifier, &organic, &700));
+        assert!(!client.is_accredited(&certifier, &organic, &900));
+    }
+
+    #[test]
+    fn test_only_admin_manages_accreditations() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, admin) = setup(&env);
+        let attacker = Address::generate(&env);
+        let new_admin = Address::generate(&env);
+        let organic = Symbol::new(&env, "organic");
+
+        let res = client.try_accredit_certifier(&attacker, &attacker, &organic, &0, &100);
+        assert_eq!(res, Err(Ok(CertifierError::Unauthorized)));
+
+        client.transfer_certifier_admin(&admin, &new_admin);
+        let res = client.try_accredit_certifier(&admin, &attacker, &organic, &0, &100);
+        assert_eq!(res, Err(Ok(CertifierError::Unauthorized)));
+        client.accredit_certifier(&new_admin, &attacker, &organic, &0, &100);
+    }
+}
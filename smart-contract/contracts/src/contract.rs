@@ -1,9 +1,11 @@
 use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Map, String, Symbol, Vec};
 
 use crate::types::{
-    Product, TrackingEvent, TrackingEventFilter, TrackingEventPage,
+    EventDevice, EventPosition, EventSequence, EventTime, Product, TimestampWindows, TrackingEvent,
+    TrackingEventFilter, TrackingEventPage,
 };
 use crate::error::Error;
+use crate::{event_chain, event_sequence, event_type_registry};
 use crate::{storage, AuthorizationContractClient};
 use crate::validation_contract::ValidationContract;
 
@@ -82,6 +84,12 @@ impl ChainLogisticsContract {
         Ok(())
     }
 
+    /// Get the admin, who also administers the TrackingContract wired to
+    /// this contract. `None` before `init`.
+    pub fn get_admin_address(env: Env) -> Option<Address> {
+        storage::get_admin(&env)
+    }
+
     pub fn is_paused(env: Env) -> bool {
         storage::is_paused(&env)
     }
@@ -111,6 +119,75 @@ impl ChainLogisticsContract {
         Ok(())
     }
 
+    // ═══════════════════════════════════════════════════════════════════════
+    // EVENT TYPES
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Set the EventTypeRegistryContract whose types `add_tracking_event`
+    /// accepts. Admin only. No events are accepted until it is set.
+    pub fn set_event_type_registry(env: Env, admin: Address, event_types: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        storage::set_event_type_registry(&env, &event_types);
+        Ok(())
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // EVENT SEQUENCING
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Set the event ordering rules for a product category, replacing any
+    /// existing ones. Tracking events for products in the category are
+    /// checked against them from then on.
+    pub fn set_event_sequence(
+        env: Env,
+        admin: Address,
+        category: String,
+        sequence: EventSequence,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        if category.len() == 0 {
+            return Err(Error::InvalidInput);
+        }
+        ValidationContract::validate_event_sequence(&sequence)?;
+        storage::set_event_sequence(&env, &category, &sequence);
+        Ok(())
+    }
+
+    /// Drop a category's event ordering rules.
+    pub fn remove_event_sequence(env: Env, admin: Address, category: String) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        storage::remove_event_sequence(&env, &category);
+        Ok(())
+    }
+
+    pub fn get_event_sequence(env: Env, category: String) -> Option<EventSequence> {
+        storage::get_event_sequence(&env, &category)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // EVENT TIME
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Set how far an actor-supplied `occurred_at` may lie before or after
+    /// the time its event is recorded. Admin only.
+    pub fn set_timestamp_windows(
+        env: Env,
+        admin: Address,
+        windows: TimestampWindows,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        storage::set_timestamp_windows(&env, &windows);
+        Ok(())
+    }
+
+    /// Get the `occurred_at` windows, the defaults until the admin sets them.
+    pub fn get_timestamp_windows(env: Env) -> TimestampWindows {
+        storage::get_timestamp_windows(&env).unwrap_or(TimestampWindows {
+            max_backdate_secs: ValidationContract::DEFAULT_MAX_BACKDATE_SECS,
+            max_forward_skew_secs: ValidationContract::DEFAULT_MAX_FORWARD_SKEW_SECS,
+        })
+    }
+
     // Note: register_product, deactivate_product, reactivate_product,
     // get_product, and get_stats have been extracted to ProductRegistryContract
     // in product_registry.rs
@@ -131,31 +208,49 @@ impl ChainLogisticsContract {
         require_not_paused(&env)?;
         let product = read_product(&env, &product_id)?;
         require_can_add_event(&env, &product_id, &product, &actor)?;
+        let event_types = storage::get_event_type_registry(&env).ok_or(Error::NotInitialized)?;
+        event_type_registry::check_registered_event_type(&env, &event_types, &event_type, &metadata)?;
+        if storage::get_product_recall(&env, &product_id).is_some() {
+            ValidationContract::validate_event_for_recalled(&env, &event_type)?;
+        }
 
         ValidationContract::validate_event_location(&location)?;
         ValidationContract::validate_event_note(&note)?;
         ValidationContract::validate_metadata(&metadata)?;
+        let sequence = storage::get_event_sequence(&env, &product.category);
+        if let Some(sequence) = &sequence {
+            event_sequence::check(&env, sequence, &product_id, &event_type)?;
+        }
 
         let event_id = storage::next_event_id(&env);
         let event = TrackingEvent {
             event_id,
             product_id: product_id.clone(),
             actor,
-            timestamp: env.ledger().timestamp(),
+            occurred_at: env.ledger().timestamp(),
+            recorded_at: env.ledger().timestamp(),
             event_type: event_type.clone(),
             location,
+            position: EventPosition::None,
+            readings: Vec::new(&env),
+            device: EventDevice::None,
             data_hash,
+            prev_event_hash: event_chain::head_hash(&env, &product_id),
             note,
             metadata,
         };
 
         storage::put_event(&env, &event);
+        event_chain::advance_head(&env, &product_id, &event);
 
         let mut ids = storage::get_product_event_ids(&env, &product_id);
         ids.push_back(event_id);
         storage::put_product_event_ids(&env, &product_id, &ids);
 
         storage::index_event_by_type(&env, &product_id, &event_type, event_id);
+        if let Some(sequence) = &sequence {
+            event_sequence::record(&env, sequence, &product_id, &event);
+        }
 
         env.events().publish(
             (
@@ -238,6 +333,7 @@ impl ChainLogisticsContract {
     pub fn get_events_by_time_range(
         env: Env,
         product_id: String,
+        time_field: EventTime,
         start_time: u64,
         end_time: u64,
         offset: u64,
@@ -251,7 +347,8 @@ impl ChainLogisticsContract {
         for i in 0..all_ids.len() {
             let eid = all_ids.get_unchecked(i);
             if let Some(event) = storage::get_event(&env, eid) {
-                if event.timestamp >= start_time && event.timestamp <= end_time {
+                let time = event.time(time_field);
+                if time >= start_time && time <= end_time {
                     matching_ids.push_back(eid);
                 }
             }
@@ -302,10 +399,11 @@ impl ChainLogisticsContract {
                 if filter.event_type != empty_sym && event.event_type != filter.event_type {
                     matches = false;
                 }
-                if filter.start_time > 0 && event.timestamp < filter.start_time {
+                let time = event.time(filter.time_field);
+                if filter.start_time > 0 && time < filter.start_time {
                     matches = false;
                 }
-                if filter.end_time < u64::MAX && event.timestamp > filter.end_time {
+                if filter.end_time < u64::MAX && time > filter.end_time {
                     matches = false;
                 }
                 if filter.location != empty_loc && event.location != filter.location {
@@ -0,0 +1,288 @@
+use soroban_sdk::{
+    contract, contractimpl, contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env, String, Symbol, Vec,
+};
+
+use crate::error::DeviceError;
+use crate::storage_contract::StorageContract;
+use crate::types::Device;
+use crate::validation_contract::ValidationContract;
+
+#[contracttype]
+#[derive(Clone)]
+enum DeviceDataKey {
+    Device(BytesN<32>),
+}
+
+// ─── Storage helpers ─────────────────────────────────────────────────────────
+
+fn get_device(env: &Env, public_key: &BytesN<32>) -> Option<Device> {
+    StorageContract::read_persistent(env, &DeviceDataKey::Device(public_key.clone()))
+}
+
+fn put_device(env: &Env, device: &Device) {
+    StorageContract::write_persistent(
+        env,
+        &DeviceDataKey::Device(device.public_key.clone()),
+        device,
+    );
+}
+
+// ─── Internal helpers ────────────────────────────────────────────────────────
+
+fn read_device(env: &Env, public_key: &BytesN<32>) -> Result<Device, DeviceError> {
+    get_device(env, public_key).ok_or(DeviceError::DeviceNotFound)
+}
+
+/// Read a device its owner is about to change.
+fn read_owned_device(env: &Env, owner: &Address, public_key: &BytesN<32>) -> Result<Device, DeviceError> {
+    owner.require_auth();
+    let device = read_device(env, public_key)?;
+    if &device.owner != owner {
+        return Err(DeviceError::Unauthorized);
+    }
+    Ok(device)
+}
+
+/// The message a device signs to prove it holds its key when it is
+/// registered to `owner`.
+fn registration_payload(env: &Env, owner: &Address) -> Bytes {
+    (env.current_contract_address(), owner.clone()).to_xdr(env)
+}
+
+fn validate_scope(products: &Vec<String>, event_types: &Vec<Symbol>) -> Result<(), DeviceError> {
+    if products.len() > ValidationContract::MAX_DEVICE_SCOPE
+        || event_types.len() > ValidationContract::MAX_DEVICE_SCOPE
+    {
+        return Err(DeviceError::InvalidInput);
+    }
+    Ok(())
+}
+
+// ─── Contract ────────────────────────────────────────────────────────────────
+
+#[contract]
+pub struct DeviceRegistryContract;
+
+#[contractimpl]
+impl DeviceRegistryContract {
+    /// Register a device to report for `owner`. The device signs the
+    /// registration payload to prove it holds `public_key`; an invalid
+    /// signature aborts the call. A revoked key can be registered again
+    /// by its owner only.
+    pub fn register_device(
+        env: Env,
+        owner: Address,
+        public_key: BytesN<32>,
+        products: Vec<String>,
+        event_types: Vec<Symbol>,
+        signature: BytesN<64>,
+    ) -> Result<(), DeviceError> {
+        owner.require_auth();
+        match get_device(&env, &public_key) {
+            None => {}
+            Some(existing) if !existing.revoked => return Err(DeviceError::InvalidInput),
+            Some(existing) if existing.owner != owner => return Err(DeviceError::Unauthorized),
+            Some(_) => {}
+        }
+        validate_scope(&products, &event_types)?;
+        env.crypto()
+            .ed25519_verify(&public_key, &registration_payload(&env, &owner), &signature);
+
+        let device = Device {
+            public_key: public_key.clone(),
+            owner,
+            products,
+            event_types,
+            registered_at: env.ledger().timestamp(),
+            revoked: false,
+        };
+        put_device(&env, &device);
+
+        env.events().publish(
+            (Symbol::new(&env, "device_registered"), public_key),
+            device,
+        );
+
+        Ok(())
+    }
+
+    /// Replace the products and event types a device may report on.
+    /// Only the device's owner can change them.
+    pub fn update_device_scope(
+        env: Env,
+        owner: Address,
+        public_key: BytesN<32>,
+        products: Vec<String>,
+        event_types: Vec<Symbol>,
+    ) -> Result<(), DeviceError> {
+        let mut device = read_owned_device(&env, &owner, &public_key)?;
+        if device.revoked {
+            return Err(DeviceError::Unauthorized);
+        }
+        validate_scope(&products, &event_types)?;
+
+        device.products = products;
+        device.event_types = event_types;
+        put_device(&env, &device);
+
+        env.events().publish(
+            (Symbol::new(&env, "device_scope_updated"), public_key),
+            device,
+        );
+
+        Ok(())
+    }
+
+    /// Revoke a device, e.g. when it is lost or its key leaks. Only the
+    /// device's owner can revoke it.
+    pub fn revoke_device(env: Env, owner: Address, public_key: BytesN<32>) -> Result<(), DeviceError> {
+        let mut device = read_owned_device(&env, &owner, &public_key)?;
+        if device.revoked {
+            return Err(DeviceError::InvalidInput);
+        }
+
+        device.revoked = true;
+        put_device(&env, &device);
+
+        env.events().publish(
+            (Symbol::new(&env, "device_revoked"), public_key),
+            owner,
+        );
+
+        Ok(())
+    }
+
+    /// Get the message a device signs to be registered to `owner`.
+    pub fn get_registration_payload(env: Env, owner: Address) -> Bytes {
+        registration_payload(&env, &owner)
+    }
+
+    /// Get a registered device.
+    pub fn get_device(env: Env, public_key: BytesN<32>) -> Result<Device, DeviceError> {
+        read_device(&env, &public_key)
+    }
+
+    /// Check that a device may report an event of `event_type` on
+    /// `product_id`, returning the owner it reports for.
+    pub fn check_device(
+        env: Env,
+        public_key: BytesN<32>,
+        product_id: String,
+        event_type: Symbol,
+    ) -> Result<Address, DeviceError> {
+        let device = read_device(&env, &public_key)?;
+        if device.revoked
+            || (!device.products.is_empty() && !device.products.contains(&product_id))
+            || (!device.event_types.is_empty() && !device.event_types.contains(&event_type))
+        {
+            return Err(DeviceError::Unauthorized);
+        }
+        Ok(device.owner)
+    }
+}
+
+#[cfg(test)]
+mod test_device_registry {
+    use super::*;
+    use soroban_sdk::{testutils::Address as _, vec};
+
+    fn setup(env: &Env) -> DeviceRegistryContractClient<'_> {
+        let contract_id = env.register_contract(None, DeviceRegistryContract);
+        DeviceRegistryContractClient::new(env, &contract_id)
+    }
+
+    /// A device key and its signature over the payload registering it to `owner`.
+    fn device_key(
+        env: &Env,
+        client: &DeviceRegistryContractClient,
+        seed: u8,
+        owner: &Address,
+    ) -> (BytesN<32>, BytesN<64>) {
+        use ed25519_dalek::Signer;
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[seed; 32]);
+        let payload = client.get_registration_payload(owner);
+        let mut buf = [0u8; 256];
+        let len = payload.len() as usize;
+        payload.copy_into_slice(&mut buf[..len]);
+        (
+            BytesN::from_array(env, &signing_key.verifying_key().to_bytes()),
+            BytesN::from_array(env, &signing_key.sign(&buf[..len]).to_bytes()),
+        )
+    }
+
+    #[test]
+    fn test_register_and_check_device() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let client = setup(&env);
+        let org = Address::generate(&env);
+        let (key, signature) = device_key(&env, &client, 7, &org);
+        let product = String::from_str(&env, "PROD1");
+        let sensed = Symbol::new(&env, "sensed");
+
+        client.register_device(&org, &key, &vec![&env, product.clone()], &vec![&env, sensed.clone()], &signature);
+        assert_eq!(client.get_device(&key).owner, org);
+        assert_eq!(client.check_device(&key, &product, &sensed), org);
+
+        let res = client.try_check_device(&key, &String::from_str(&env, "PROD2"), &sensed);
+        assert_eq!(res, Err(Ok(DeviceError::Unauthorized)));
+        let res = client.try_check_device(&key, &product, &Symbol::new(&env, "shipped"));
+        assert_eq!(res, Err(Ok(DeviceError::Unauthorized)));
+
+        // An empty scope allows anything
+        client.update_device_scope(&org, &key, &vec![&env], &vec![&env]);
+        client.check_device(&key, &String::from_str(&env, "PROD2"), &Symbol::new(&env, "shipped"));
+
+        let res = client.try_register_device(&org, &key, &vec![&env], &vec![&env], &signature);
+        assert_eq!(res, Err(Ok(DeviceError::InvalidInput)));
+        let res = client.try_check_device(&BytesN::from_array(&env, &[8; 32]), &product, &sensed);
+        assert_eq!(res, Err(Ok(DeviceError::DeviceNotFound)));
+    }
+
+    #[test]
+    fn test_only_owner_revokes_device() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let client = setup(&env);
+        let org = Address::generate(&env);
+        let (key, signature) = device_key(&env, &client, 7, &org);
+        client.register_device(&org, &key, &vec![&env], &vec![&env], &signature);
+
+        let res = client.try_revoke_device(&Address::generate(&env), &key);
+        assert_eq!(res, Err(Ok(DeviceError::Unauthorized)));
+
+        client.revoke_device(&org, &key);
+        assert!(client.get_device(&key).revoked);
+        let res = client.try_check_device(&key, &String::from_str(&env, "PROD1"), &Symbol::new(&env, "sensed"));
+        assert_eq!(res, Err(Ok(DeviceError::Unauthorized)));
+        let res = client.try_update_device_scope(&org, &key, &vec![&env], &vec![&env]);
+        assert_eq!(res, Err(Ok(DeviceError::Unauthorized)));
+        let res = client.try_revoke_device(&org, &key);
+        assert_eq!(res, Err(Ok(DeviceError::InvalidInput)));
+    }
+
+    #[test]
+    fn test_owner_registers_revoked_device_again() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let client = setup(&env);
+        let org = Address::generate(&env);
+        let (key, signature) = device_key(&env, &client, 7, &org);
+        client.register_device(&org, &key, &vec![&env], &vec![&env], &signature);
+        client.revoke_device(&org, &key);
+
+        // Another owner cannot take over a revoked key
+        let stranger = Address::generate(&env);
+        let (_, stranger_signature) = device_key(&env, &client, 7, &stranger);
+        let res = client.try_register_device(&stranger, &key, &vec![&env], &vec![&env], &stranger_signature);
+        assert_eq!(res, Err(Ok(DeviceError::Unauthorized)));
+
+        client.register_device(&org, &key, &vec![&env], &vec![&env], &signature);
+        assert!(!client.get_device(&key).revoked);
+        client.check_device(&key, &String::from_str(&env, "PROD1"), &Symbol::new(&env, "sensed"));
+    }
+
+}
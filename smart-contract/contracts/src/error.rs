@@ -3,6 +3,8 @@ use soroban_sdk::contracterror;
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
+// Contract specs allow at most 50 error cases, so the certifier and device
+// registries have their own enums below, reusing the same codes.
 pub enum Error {
     // --- Core (1-10) ---
     ProductAlreadyExists = 1,
@@ -41,4 +43,73 @@ pub enum Error {
     ProductDeactivated = 40,
     DeactivationReasonRequired = 41,
     ProductAlreadyActive = 42,
+
+    // --- Updates (50-60) ---
+    NoChangesInUpdate = 50,
+    RevisionNotFound = 51,
+
+    // --- Composition (60-70) ---
+    ProductConsumed = 60,
+    InvalidComponent = 61,
+    MergeRequiresMultipleSources = 62,
+
+    // --- Recalls (70-80) ---
+    ProductRecalled = 70,
+    ProductAlreadyRecalled = 71,
+    RecallReasonRequired = 72,
+
+    // --- Status (80-90) ---
+    InvalidStatusTransition = 80,
+
+    // --- Certifications (90-100) ---
+    CertificationNotFound = 90,
+    CertificationRevoked = 91,
+    InvalidCertificationExpiry = 92,
+    CertifierNotAccredited = 93,
+
+    // --- GS1 identifiers (100-110) ---
+    InvalidIdentifier = 100,
+
+    // --- Aliases (110-120) ---
+    AliasAlreadyExists = 110,
+
+    // --- Event types (120-130) ---
+    UnknownEventType = 120,
+
+    // --- Event sequencing (130-140) ---
+    InvalidEventSequence = 130,
+
+    // --- Geolocation (140-150) ---
+    InvalidGeoPoint = 140,
+
+    // --- Devices (150-160) ---
+    InvalidNonce = 151,
+
+    // --- Event time (160-170) ---
+    InvalidTimestamp = 160,
+}
+
+/// Errors of the `CertifierRegistryContract`.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum CertifierError {
+    Unauthorized = 3,
+    InvalidInput = 4,
+    NotInitialized = 6,
+    AlreadyInitialized = 7,
+    InvalidAccreditationWindow = 94,
+    AccreditationNotFound = 95,
+    CertifierAlreadySuspended = 96,
+    CertifierNotSuspended = 97,
+}
+
+/// Errors of the `DeviceRegistryContract`.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum DeviceError {
+    Unauthorized = 3,
+    InvalidInput = 4,
+    DeviceNotFound = 150,
 }
\ No newline at end of file
@@ -0,0 +1,65 @@
+use soroban_sdk::{xdr::ToXdr, BytesN, Env, String};
+
+use crate::storage;
+use crate::types::TrackingEvent;
+
+// Each product's events form a hash chain: an event carries the SHA-256 of
+// the previous event's XDR, and the product's head hash is that of its
+// latest event. Off-chain copies can be checked against the head alone.
+// An aggregate event is shared by several products; its record carries the
+// link for `event.product_id` and the others' links are stored beside it.
+
+/// `prev_event_hash` of a product's first event.
+pub fn genesis_hash(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[0; 32])
+}
+
+pub fn hash_event(env: &Env, event: &TrackingEvent) -> BytesN<32> {
+    env.crypto().sha256(&event.clone().to_xdr(env))
+}
+
+/// Hash the next event of a product must link to.
+pub fn head_hash(env: &Env, product_id: &String) -> BytesN<32> {
+    storage::get_product_event_head(env, product_id).unwrap_or(genesis_hash(env))
+}
+
+/// Move a product's head to a newly stored event.
+pub fn advance_head(env: &Env, product_id: &String, event: &TrackingEvent) {
+    storage::set_product_event_head(env, product_id, &hash_event(env, event));
+}
+
+/// The hash an event links to in a product's chain.
+fn prev_link(env: &Env, product_id: &String, event: &TrackingEvent) -> Option<BytesN<32>> {
+    if event.product_id == *product_id {
+        Some(event.prev_event_hash.clone())
+    } else {
+        storage::get_aggregate_link(env, event.event_id, product_id)
+    }
+}
+
+/// Walk a product's events from the first and return the ID of the first
+/// one whose link doesn't match, or `None` if the chain is intact. If the
+/// links hold but the head doesn't match the latest event, the latest
+/// event's ID is returned.
+pub fn find_broken_link(env: &Env, product_id: &String) -> Option<u64> {
+    let ids = storage::get_product_event_ids(env, product_id);
+    let mut expected = genesis_hash(env);
+
+    for event_id in ids.iter() {
+        let event = match storage::get_event(env, event_id) {
+            Some(event) => event,
+            None => return Some(event_id),
+        };
+        if prev_link(env, product_id, &event) != Some(expected) {
+            return Some(event_id);
+        }
+        expected = hash_event(env, &event);
+    }
+
+    match ids.last() {
+        Some(last) if storage::get_product_event_head(env, product_id) != Some(expected) => {
+            Some(last)
+        }
+        _ => None,
+    }
+}
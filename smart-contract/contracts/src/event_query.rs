@@ -1,7 +1,9 @@
 use soroban_sdk::{contract, contractimpl, Address, Env, String, Symbol, Vec, contracttype};
 
 use crate::error::Error;
-use crate::types::TrackingEventFilter;
+use crate::storage_contract::StorageContract;
+use crate::geo;
+use crate::types::{EventPosition, EventTime, GeoBox, GeoPoint, TrackingEvent, TrackingEventFilter};
 use crate::types::TrackingEventPage;
 use crate::{ProductRegistryContractClient, TrackingContractClient};
 
@@ -13,19 +15,19 @@ enum QueryDataKey {
 }
 
 fn get_registry_contract(env: &Env) -> Option<Address> {
-    env.storage().persistent().get(&QueryDataKey::RegistryContract)
+    StorageContract::read_instance(env, &QueryDataKey::RegistryContract)
 }
 
 fn set_registry_contract(env: &Env, address: &Address) {
-    env.storage().persistent().set(&QueryDataKey::RegistryContract, address);
+    StorageContract::write_instance(env, &QueryDataKey::RegistryContract, address);
 }
 
 fn get_tracking_contract(env: &Env) -> Option<Address> {
-    env.storage().persistent().get(&QueryDataKey::TrackingContract)
+    StorageContract::read_instance(env, &QueryDataKey::TrackingContract)
 }
 
 fn set_tracking_contract(env: &Env, address: &Address) {
-    env.storage().persistent().set(&QueryDataKey::TrackingContract, address);
+    StorageContract::write_instance(env, &QueryDataKey::TrackingContract, address);
 }
 
 fn ensure_product_exists(env: &Env, product_id: &String) -> Result<(), Error> {
@@ -38,6 +40,51 @@ fn ensure_product_exists(env: &Env, product_id: &String) -> Result<(), Error> {
     }
 }
 
+/// Page through a product's events that have a position matching `matches`.
+fn positioned_events_page(
+    env: &Env,
+    product_id: &String,
+    offset: u64,
+    limit: u64,
+    matches: impl Fn(&GeoPoint) -> bool,
+) -> Result<TrackingEventPage, Error> {
+    if limit == 0 || limit > 1000 {
+        return Ok(TrackingEventPage {
+            events: Vec::new(env),
+            total_count: 0,
+            has_more: false,
+        });
+    }
+
+    ensure_product_exists(env, product_id)?;
+
+    let tracking = get_tracking_contract(env).ok_or(Error::NotInitialized)?;
+    let tracking_client = TrackingContractClient::new(env, &tracking);
+
+    let mut matching: Vec<TrackingEvent> = Vec::new(env);
+    for eid in tracking_client.get_product_event_ids(product_id).iter() {
+        if let Ok(Ok(event)) = tracking_client.try_get_event(&eid) {
+            if event.position.point().is_some_and(|position| matches(&position)) {
+                matching.push_back(event);
+            }
+        }
+    }
+
+    let total_count = matching.len() as u64;
+    let mut events = Vec::new(env);
+    if offset < total_count {
+        let end = ((offset + limit) as u32).min(matching.len());
+        events = matching.slice(offset as u32..end);
+    }
+    let has_more = offset + (events.len() as u64) < total_count;
+
+    Ok(TrackingEventPage {
+        events,
+        total_count,
+        has_more,
+    })
+}
+
 // ─── Contract ────────────────────────────────────────────────────────────────
 
 #[contract]
@@ -186,6 +233,7 @@ impl EventQueryContract {
     pub fn get_events_by_time_range(
         env: Env,
         product_id: String,
+        time_field: EventTime,
         start_time: u64,
         end_time: u64,
         offset: u64,
@@ -212,7 +260,8 @@ impl EventQueryContract {
             if let Some(eid) = all_ids.get(i) {
                 if let Ok(event) = tracking_client.try_get_event(&eid) {
                     if let Ok(event) = event {
-                        if event.timestamp >= start_time && event.timestamp <= end_time {
+                        let time = event.time(time_field);
+                        if time >= start_time && time <= end_time {
                             matching_ids.push_back(eid);
                         }
                     }
@@ -291,10 +340,11 @@ impl EventQueryContract {
                         if filter.event_type != empty_sym && event.event_type != filter.event_type {
                             matches = false;
                         }
-                        if filter.start_time > 0 && event.timestamp < filter.start_time {
+                        let time = event.time(filter.time_field);
+                        if filter.start_time > 0 && time < filter.start_time {
                             matches = false;
                         }
-                        if filter.end_time < u64::MAX && event.timestamp > filter.end_time {
+                        if filter.end_time < u64::MAX && time > filter.end_time {
                             matches = false;
                         }
                         if filter.location != empty_loc && event.location != filter.location {
@@ -342,6 +392,60 @@ impl EventQueryContract {
         })
     }
 
+    /// Get a product's events positioned inside a latitude/longitude box.
+    pub fn get_events_in_box(
+        env: Env,
+        product_id: String,
+        area: GeoBox,
+        offset: u64,
+        limit: u64,
+    ) -> Result<TrackingEventPage, Error> {
+        if !geo::is_valid_box(&area) {
+            return Err(Error::InvalidGeoPoint);
+        }
+        positioned_events_page(&env, &product_id, offset, limit, |position| {
+            geo::in_box(&area, position)
+        })
+    }
+
+    /// Get a product's events positioned within `radius_m` metres of
+    /// `center`. Fix accuracy is not taken into account.
+    pub fn get_events_in_radius(
+        env: Env,
+        product_id: String,
+        center: GeoPoint,
+        radius_m: u32,
+        offset: u64,
+        limit: u64,
+    ) -> Result<TrackingEventPage, Error> {
+        if !geo::is_valid_point(&center) {
+            return Err(Error::InvalidGeoPoint);
+        }
+        positioned_events_page(&env, &product_id, offset, limit, |position| {
+            geo::within_radius(&center, position, radius_m)
+        })
+    }
+
+    /// Get a product's latest event that carries a position, if any.
+    pub fn get_last_known_position(
+        env: Env,
+        product_id: String,
+    ) -> Result<Option<TrackingEvent>, Error> {
+        ensure_product_exists(&env, &product_id)?;
+
+        let tracking = get_tracking_contract(&env).ok_or(Error::NotInitialized)?;
+        let tracking_client = TrackingContractClient::new(&env, &tracking);
+
+        for eid in tracking_client.get_product_event_ids(&product_id).iter().rev() {
+            if let Ok(Ok(event)) = tracking_client.try_get_event(&eid) {
+                if event.position != EventPosition::None {
+                    return Ok(Some(event));
+                }
+            }
+        }
+        Ok(None)
+    }
+
     /// Get total event count for a product.
     pub fn get_event_count(env: Env, product_id: String) -> Result<u64, Error> {
         ensure_product_exists(&env, &product_id)?;
@@ -370,12 +474,13 @@ impl EventQueryContract {
 #[cfg(test)]
 mod test_event_query {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, Map, Vec};
+    use soroban_sdk::{testutils::{Address as _, Ledger}, Address, BytesN, Env, Map, Vec};
     use crate::{
         AuthorizationContract, AuthorizationContractClient, ChainLogisticsContract, ChainLogisticsContractClient,
+        EventTypeRegistryContract, EventTypeRegistryContractClient,
         ProductRegistryContract, ProductRegistryContractClient,
-        ProductConfig,
-        TrackingContract, TrackingContractClient,
+        IdScheme, ProductConfig,
+        TrackingContract, TrackingContractClient, TrackingEventInput,
     };
 
     fn setup(env: &Env) -> (ProductRegistryContractClient, TrackingContractClient, super::EventQueryContractClient, Address, Address) {
@@ -398,8 +503,24 @@ mod test_event_query {
         registry_client.configure_auth_contract(&auth_id);
 
         tracking_client.init(&cl_id);
+        registry_client.configure_admin(&admin);
+        registry_client.configure_tracking_contract(&admin, &tracking_id);
+        tracking_client.configure_registry(&admin, &registry_id);
         query_client.init(&registry_id, &tracking_id);
 
+        let event_types_id = env.register_contract(None, EventTypeRegistryContract);
+        let event_types_client = EventTypeRegistryContractClient::new(env, &event_types_id);
+        event_types_client.event_types_init(&admin);
+        for event_type in ["created", "shipped", "received", "inspected", "scanned", "caught", "landed"] {
+            event_types_client.register_event_type(
+                &admin,
+                &Symbol::new(env, event_type),
+                &String::from_str(env, ""),
+                &Vec::new(env),
+            );
+        }
+        tracking_client.configure_event_types(&admin, &event_types_id);
+
         (registry_client, tracking_client, query_client, registry_id, tracking_id)
     }
 
@@ -414,6 +535,7 @@ mod test_event_query {
             owner,
             &ProductConfig {
                 id: product_id.clone(),
+                id_scheme: IdScheme::Custom,
                 name: String::from_str(env, "Test Product"),
                 description: String::from_str(env, "Description"),
                 origin_location: String::from_str(env, "Origin"),
@@ -568,7 +690,7 @@ mod test_event_query {
         add_test_event(&env, &tracking_client, &owner, &product_id, "shipped");
 
         // Get events in time range (all events)
-        let result = query_client.get_events_by_time_range(&product_id, &0, &u64::MAX, &0, &10);
+        let result = query_client.get_events_by_time_range(&product_id, &EventTime::RecordedAt, &0, &u64::MAX, &0, &10);
         assert_eq!(result.events.len(), 2);
         assert_eq!(result.total_count, 2);
     }
@@ -589,6 +711,7 @@ mod test_event_query {
         // Filter by type
         let filter = TrackingEventFilter {
             event_type: Symbol::new(&env, "created"),
+            time_field: EventTime::RecordedAt,
             start_time: 0,
             end_time: u64::MAX,
             location: String::from_str(&env, ""),
@@ -708,6 +831,7 @@ mod test_event_query {
 
         let filter = TrackingEventFilter {
             event_type: Symbol::new(&env, ""),
+            time_field: EventTime::RecordedAt,
             start_time: 0,
             end_time: u64::MAX,
             location: String::from_str(&env, ""),
@@ -737,13 +861,14 @@ mod test_event_query {
         assert_eq!(result.total_count, 0);
         assert!(!result.has_more);
 
-        let result = query_client.get_events_by_time_range(&product_id, &0, &u64::MAX, &0, &1001);
+        let result = query_client.get_events_by_time_range(&product_id, &EventTime::RecordedAt, &0, &u64::MAX, &0, &1001);
         assert_eq!(result.events.len(), 0);
         assert_eq!(result.total_count, 0);
         assert!(!result.has_more);
 
         let filter = TrackingEventFilter {
             event_type: Symbol::new(&env, "created"),
+            time_field: EventTime::RecordedAt,
             start_time: 0,
             end_time: u64::MAX,
             location: String::from_str(&env, ""),
@@ -780,4 +905,149 @@ mod test_event_query {
         let res = query_client.try_get_event_count(&fake_id);
         assert_eq!(res, Err(Ok(Error::NotInitialized)));
     }
+
+    fn add_positioned_event(
+        env: &Env,
+        tracking_client: &TrackingContractClient,
+        owner: &Address,
+        product_id: &String,
+        lat_e7: i64,
+        lon_e7: i64,
+    ) -> u64 {
+        tracking_client.submit_tracking_event(
+            owner,
+            &TrackingEventInput {
+                product_id: product_id.clone(),
+                event_type: Symbol::new(env, "scanned"),
+                occurred_at: 0,
+                location: String::from_str(env, ""),
+                position: EventPosition::Point(GeoPoint { lat_e7, lon_e7, accuracy_m: 15 }),
+                readings: Vec::new(env),
+                data_hash: BytesN::from_array(env, &[0; 32]),
+                note: String::from_str(env, ""),
+                metadata: Map::new(env),
+            },
+        )
+    }
+
+    #[test]
+    fn test_geofence_queries() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (registry_client, tracking_client, query_client, _registry_id, _tracking_id) = setup(&env);
+        let owner = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &owner, "PROD1");
+
+        assert_eq!(query_client.get_last_known_position(&product_id), None);
+
+        // Rotterdam, Amsterdam, then an event without a position
+        let rotterdam = add_positioned_event(&env, &tracking_client, &owner, &product_id, 519_000_000, 44_800_000);
+        let amsterdam = add_positioned_event(&env, &tracking_client, &owner, &product_id, 523_791_000, 49_003_000);
+        add_test_event(&env, &tracking_client, &owner, &product_id, "inspected");
+
+        let netherlands = GeoBox {
+            min_lat_e7: 507_000_000,
+            max_lat_e7: 535_000_000,
+            min_lon_e7: 33_000_000,
+            max_lon_e7: 72_000_000,
+        };
+        let page = query_client.get_events_in_box(&product_id, &netherlands, &0, &10);
+        assert_eq!(page.total_count, 2);
+
+        let south_holland = GeoBox { max_lat_e7: 521_000_000, ..netherlands.clone() };
+        let page = query_client.get_events_in_box(&product_id, &south_holland, &0, &10);
+        assert_eq!(page.total_count, 1);
+        assert_eq!(page.events.get_unchecked(0).event_id, rotterdam);
+
+        let center = GeoPoint { lat_e7: 523_700_000, lon_e7: 48_900_000, accuracy_m: 0 };
+        let page = query_client.get_events_in_radius(&product_id, &center, &5_000, &0, &10);
+        assert_eq!(page.total_count, 1);
+        assert_eq!(page.events.get_unchecked(0).event_id, amsterdam);
+        let page = query_client.get_events_in_radius(&product_id, &center, &100_000, &1, &10);
+        assert_eq!(page.total_count, 2);
+        assert_eq!(page.events.len(), 1);
+
+        let last = query_client.get_last_known_position(&product_id).unwrap();
+        assert_eq!(last.event_id, amsterdam);
+
+        let inverted = GeoBox { min_lat_e7: 1, max_lat_e7: 0, ..netherlands };
+        assert_eq!(
+            query_client.try_get_events_in_box(&product_id, &inverted, &0, &10),
+            Err(Ok(Error::InvalidGeoPoint))
+        );
+    }
+
+    #[test]
+    fn test_event_position_is_validated() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (registry_client, tracking_client, _query_client, _registry_id, _tracking_id) = setup(&env);
+        let owner = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &owner, "PROD1");
+
+        let res = tracking_client.try_submit_tracking_event(
+            &owner,
+            &TrackingEventInput {
+                product_id: product_id.clone(),
+                event_type: Symbol::new(&env, "scanned"),
+                occurred_at: 0,
+                location: String::from_str(&env, ""),
+                position: EventPosition::Point(GeoPoint { lat_e7: 910_000_000, lon_e7: 0, accuracy_m: 15 }),
+                readings: Vec::new(&env),
+                data_hash: BytesN::from_array(&env, &[0; 32]),
+                note: String::from_str(&env, ""),
+                metadata: Map::new(&env),
+            },
+        );
+        assert_eq!(res, Err(Ok(Error::InvalidGeoPoint)));
+        assert_eq!(tracking_client.get_event_count(&product_id), 0);
+    }
+
+    #[test]
+    fn test_time_filters_use_either_timestamp() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (registry_client, tracking_client, query_client, _registry_id, _tracking_id) = setup(&env);
+        let owner = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &owner, "PROD1");
+        env.ledger().with_mut(|l| l.timestamp = 100_000);
+
+        // Caught at sea the day before, recorded on landing
+        tracking_client.submit_tracking_event(
+            &owner,
+            &TrackingEventInput {
+                product_id: product_id.clone(),
+                event_type: Symbol::new(&env, "caught"),
+                occurred_at: 100_000 - 86_400,
+                location: String::from_str(&env, ""),
+                position: EventPosition::None,
+                readings: Vec::new(&env),
+                data_hash: BytesN::from_array(&env, &[0; 32]),
+                note: String::from_str(&env, ""),
+                metadata: Map::new(&env),
+            },
+        );
+        add_test_event(&env, &tracking_client, &owner, &product_id, "landed");
+
+        let yesterday = (0, 100_000 - 1);
+        let result = query_client.get_events_by_time_range(&product_id, &EventTime::OccurredAt, &yesterday.0, &yesterday.1, &0, &10);
+        assert_eq!(result.total_count, 1);
+        assert_eq!(result.events.get_unchecked(0).event_type, Symbol::new(&env, "caught"));
+        let result = query_client.get_events_by_time_range(&product_id, &EventTime::RecordedAt, &yesterday.0, &yesterday.1, &0, &10);
+        assert_eq!(result.total_count, 0);
+
+        let filter = TrackingEventFilter {
+            event_type: Symbol::new(&env, ""),
+            time_field: EventTime::OccurredAt,
+            start_time: 100_000,
+            end_time: u64::MAX,
+            location: String::from_str(&env, ""),
+        };
+        let result = query_client.get_filtered_events(&product_id, &filter, &0, &10);
+        assert_eq!(result.total_count, 1);
+        assert_eq!(result.events.get_unchecked(0).event_type, Symbol::new(&env, "landed"));
+    }
 }
@@ -0,0 +1,88 @@
+use soroban_sdk::{xdr::ToXdr, BytesN, Env, String, Symbol, Vec};
+
+use crate::error::Error;
+use crate::storage;
+use crate::types::{EventSequence, SequenceMark, TrackingEvent};
+
+// Checks of a product's tracking events against its category's event
+// sequence. Events whose type the sequence doesn't name are ignored.
+
+/// Most events read to re-derive a product's last sequenced event when its
+/// mark was taken under another sequence, or it has none.
+const MAX_RESCAN: usize = 20;
+
+fn is_sequenced(sequence: &EventSequence, event_type: &Symbol) -> bool {
+    sequence.first.contains(event_type)
+        || sequence
+            .rules
+            .iter()
+            .any(|rule| rule.from == *event_type || rule.next.contains(event_type))
+}
+
+fn sequence_hash(env: &Env, sequence: &EventSequence) -> BytesN<32> {
+    env.crypto().sha256(&sequence.clone().to_xdr(env))
+}
+
+/// Type of the product's latest event that the sequence names. Read from
+/// the product's mark if it was taken under this sequence. Otherwise the
+/// latest `MAX_RESCAN` events are scanned, falling back to the marked type
+/// if the sequence still names it.
+fn last_sequenced_type(env: &Env, sequence: &EventSequence, product_id: &String) -> Option<Symbol> {
+    let mark = storage::get_sequence_mark(env, product_id);
+    if let Some(mark) = &mark {
+        if mark.sequence_hash == sequence_hash(env, sequence) {
+            return Some(mark.event_type.clone());
+        }
+    }
+
+    let ids = storage::get_product_event_ids(env, product_id);
+    for event_id in ids.iter().rev().take(MAX_RESCAN) {
+        if let Some(event) = storage::get_event(env, event_id) {
+            if is_sequenced(sequence, &event.event_type) {
+                return Some(event.event_type);
+            }
+        }
+    }
+    mark.map(|mark| mark.event_type)
+        .filter(|event_type| is_sequenced(sequence, event_type))
+}
+
+/// Mark a newly linked event as the product's latest sequenced event if
+/// the sequence names its type.
+pub fn record(env: &Env, sequence: &EventSequence, product_id: &String, event: &TrackingEvent) {
+    if is_sequenced(sequence, &event.event_type) {
+        let mark = SequenceMark {
+            sequence_hash: sequence_hash(env, sequence),
+            event_id: event.event_id,
+            event_type: event.event_type.clone(),
+        };
+        storage::set_sequence_mark(env, product_id, &mark);
+    }
+}
+
+/// Sequenced event types the product's next event may have.
+pub fn allowed_next(env: &Env, sequence: &EventSequence, product_id: &String) -> Vec<Symbol> {
+    match last_sequenced_type(env, sequence, product_id) {
+        None => sequence.first.clone(),
+        Some(last) => sequence
+            .rules
+            .iter()
+            .find(|rule| rule.from == last)
+            .map(|rule| rule.next)
+            .unwrap_or(Vec::new(env)),
+    }
+}
+
+pub fn check(
+    env: &Env,
+    sequence: &EventSequence,
+    product_id: &String,
+    event_type: &Symbol,
+) -> Result<(), Error> {
+    if is_sequenced(sequence, event_type)
+        && !allowed_next(env, sequence, product_id).contains(event_type)
+    {
+        return Err(Error::InvalidEventSequence);
+    }
+    Ok(())
+}
@@ -0,0 +1,336 @@
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Map, String, Symbol, Vec};
+
+use crate::error::Error;
+use crate::storage_contract::StorageContract;
+use crate::types::EventTypeInfo;
+use crate::validation_contract::ValidationContract;
+
+#[contracttype]
+#[derive(Clone)]
+enum EventTypeDataKey {
+    Admin,
+    EventType(Symbol),
+    TypeIndex(u32),
+    TypeCount,
+}
+
+// ─── Storage helpers ─────────────────────────────────────────────────────────
+
+fn get_admin(env: &Env) -> Option<Address> {
+    StorageContract::read_instance(env, &EventTypeDataKey::Admin)
+}
+
+fn set_admin(env: &Env, admin: &Address) {
+    StorageContract::write_instance(env, &EventTypeDataKey::Admin, admin);
+}
+
+fn get_event_type(env: &Env, event_type: &Symbol) -> Option<EventTypeInfo> {
+    StorageContract::read_persistent(env, &EventTypeDataKey::EventType(event_type.clone()))
+}
+
+fn put_event_type(env: &Env, info: &EventTypeInfo) {
+    StorageContract::write_persistent(
+        env,
+        &EventTypeDataKey::EventType(info.event_type.clone()),
+        info,
+    );
+}
+
+fn get_type_count(env: &Env) -> u32 {
+    StorageContract::read_persistent(env, &EventTypeDataKey::TypeCount).unwrap_or(0)
+}
+
+/// Append a type to the registration-ordered index.
+fn append_type_index(env: &Env, event_type: &Symbol) {
+    let count = get_type_count(env);
+    StorageContract::write_persistent(env, &EventTypeDataKey::TypeIndex(count), event_type);
+    StorageContract::write_persistent(env, &EventTypeDataKey::TypeCount, &(count + 1));
+}
+
+// ─── Internal helpers ────────────────────────────────────────────────────────
+
+fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
+    let admin = get_admin(env).ok_or(Error::NotInitialized)?;
+    caller.require_auth();
+    if &admin != caller {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn read_event_type(env: &Env, event_type: &Symbol) -> Result<EventTypeInfo, Error> {
+    get_event_type(env, event_type).ok_or(Error::UnknownEventType)
+}
+
+/// Check an event against the event type registry at `event_types`, for
+/// the contracts that record events.
+pub(crate) fn check_registered_event_type(
+    env: &Env,
+    event_types: &Address,
+    event_type: &Symbol,
+    metadata: &Map<Symbol, String>,
+) -> Result<(), Error> {
+    let event_types_client = EventTypeRegistryContractClient::new(env, event_types);
+    match event_types_client.try_check_event_type(event_type, &metadata.keys()) {
+        Ok(Ok(())) => Ok(()),
+        Err(Ok(e)) => Err(e),
+        _ => Err(Error::InvalidInput),
+    }
+}
+
+// ─── Contract ────────────────────────────────────────────────────────────────
+
+#[contract]
+pub struct EventTypeRegistryContract;
+
+#[contractimpl]
+impl EventTypeRegistryContract {
+    /// Initialize the contract with an admin address.
+    /// Can only be called once.
+    pub fn event_types_init(env: Env, admin: Address) -> Result<(), Error> {
+        if get_admin(&env).is_some() {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        set_admin(&env, &admin);
+
+        env.events().publish(
+            (Symbol::new(&env, "event_type_registry_initialized"),),
+            admin,
+        );
+
+        Ok(())
+    }
+
+    /// Get the current admin address.
+    pub fn get_event_types_admin(env: Env) -> Result<Address, Error> {
+        get_admin(&env).ok_or(Error::NotInitialized)
+    }
+
+    /// Transfer admin privileges to a new address.
+    /// Requires authentication from both current and new admin.
+    pub fn transfer_event_types_admin(
+        env: Env,
+        current_admin: Address,
+        new_admin: Address,
+    ) -> Result<(), Error> {
+        require_admin(&env, &current_admin)?;
+        new_admin.require_auth();
+        set_admin(&env, &new_admin);
+
+        env.events().publish(
+            (Symbol::new(&env, "event_types_admin_transferred"),),
+            (current_admin, new_admin),
+        );
+
+        Ok(())
+    }
+
+    /// Register an event type, with the metadata keys every event of the
+    /// type must carry. Only the admin can register; a type can be
+    /// registered once.
+    pub fn register_event_type(
+        env: Env,
+        admin: Address,
+        event_type: Symbol,
+        description: String,
+        required_metadata: Vec<Symbol>,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        if get_event_type(&env, &event_type).is_some() {
+            return Err(Error::InvalidInput);
+        }
+        ValidationContract::max_len(&description, ValidationContract::MAX_DESCRIPTION_LEN)?;
+        if required_metadata.len() > ValidationContract::MAX_METADATA_FIELDS {
+            return Err(Error::InvalidInput);
+        }
+
+        let info = EventTypeInfo {
+            event_type: event_type.clone(),
+            description,
+            required_metadata,
+            deprecated: false,
+            registered_at: env.ledger().timestamp(),
+        };
+        put_event_type(&env, &info);
+        append_type_index(&env, &event_type);
+
+        env.events().publish(
+            (Symbol::new(&env, "event_type_registered"), event_type),
+            info,
+        );
+
+        Ok(())
+    }
+
+    /// Deprecate an event type. New events of the type are rejected;
+    /// existing ones are untouched. Only the admin can deprecate.
+    pub fn deprecate_event_type(env: Env, admin: Address, event_type: Symbol) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        let mut info = read_event_type(&env, &event_type)?;
+        if info.deprecated {
+            return Err(Error::InvalidInput);
+        }
+
+        info.deprecated = true;
+        put_event_type(&env, &info);
+
+        env.events().publish(
+            (Symbol::new(&env, "event_type_deprecated"), event_type),
+            admin,
+        );
+
+        Ok(())
+    }
+
+    /// Get a registered event type.
+    pub fn get_event_type(env: Env, event_type: Symbol) -> Result<EventTypeInfo, Error> {
+        read_event_type(&env, &event_type)
+    }
+
+    /// List every registered event type, deprecated ones included, in
+    /// registration order.
+    pub fn list_event_types(env: Env) -> Vec<EventTypeInfo> {
+        let mut types = Vec::new(&env);
+        for i in 0..get_type_count(&env) {
+            if let Some(event_type) = StorageContract::read_persistent::<_, Symbol>(
+                &env,
+                &EventTypeDataKey::TypeIndex(i),
+            ) {
+                if let Some(info) = get_event_type(&env, &event_type) {
+                    types.push_back(info);
+                }
+            }
+        }
+        types
+    }
+
+    /// Check that an event of the given type may be recorded: the type is
+    /// registered and not deprecated, and `metadata_keys` holds every
+    /// required key.
+    pub fn check_event_type(
+        env: Env,
+        event_type: Symbol,
+        metadata_keys: Vec<Symbol>,
+    ) -> Result<(), Error> {
+        let info = read_event_type(&env, &event_type)?;
+        if info.deprecated {
+            return Err(Error::UnknownEventType);
+        }
+        for key in info.required_metadata.iter() {
+            if !metadata_keys.contains(&key) {
+                return Err(Error::InvalidInput);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_event_type_registry {
+    use super::*;
+    use soroban_sdk::{testutils::Address as _, vec};
+
+    fn setup(env: &Env) -> (EventTypeRegistryContractClient<'_>, Address) {
+        let contract_id = env.register_contract(None, EventTypeRegistryContract);
+        let client = EventTypeRegistryContractClient::new(env, &contract_id);
+        let admin = Address::generate(env);
+        client.event_types_init(&admin);
+        (client, admin)
+    }
+
+    #[test]
+    fn test_init() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, admin) = setup(&env);
+        assert_eq!(client.get_event_types_admin(), admin);
+
+        let res = client.try_event_types_init(&admin);
+        assert_eq!(res, Err(Ok(Error::AlreadyInitialized)));
+    }
+
+    #[test]
+    fn test_register_and_list() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, admin) = setup(&env);
+        let shipped = Symbol::new(&env, "shipped");
+        let inspected = Symbol::new(&env, "inspected");
+        let inspector = Symbol::new(&env, "inspector");
+
+        client.register_event_type(
+            &admin,
+            &shipped,
+            &String::from_str(&env, "Left the site"),
+            &vec![&env],
+        );
+        client.register_event_type(
+            &admin,
+            &inspected,
+            &String::from_str(&env, "Quality inspection"),
+            &vec![&env, inspector.clone()],
+        );
+
+        let types = client.list_event_types();
+        assert_eq!(types.len(), 2);
+        assert_eq!(types.get_unchecked(0).event_type, shipped);
+        assert_eq!(types.get_unchecked(1).required_metadata, vec![&env, inspector]);
+
+        let res = client.try_register_event_type(&admin, &shipped, &String::from_str(&env, ""), &vec![&env]);
+        assert_eq!(res, Err(Ok(Error::InvalidInput)));
+
+        let res = client.try_get_event_type(&Symbol::new(&env, "shiped"));
+        assert_eq!(res, Err(Ok(Error::UnknownEventType)));
+    }
+
+    #[test]
+    fn test_check_event_type() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, admin) = setup(&env);
+        let inspected = Symbol::new(&env, "inspected");
+        let inspector = Symbol::new(&env, "inspector");
+        client.register_event_type(
+            &admin,
+            &inspected,
+            &String::from_str(&env, "Quality inspection"),
+            &vec![&env, inspector.clone()],
+        );
+
+        client.check_event_type(&inspected, &vec![&env, inspector.clone()]);
+        let res = client.try_check_event_type(&inspected, &vec![&env]);
+        assert_eq!(res, Err(Ok(Error::InvalidInput)));
+        let misspelled = Symbol::new(&env, "Inspected");
+        let res = client.try_check_event_type(&misspelled, &vec![&env, inspector.clone()]);
+        assert_eq!(res, Err(Ok(Error::UnknownEventType)));
+
+        client.deprecate_event_type(&admin, &inspected);
+        assert!(client.get_event_type(&inspected).deprecated);
+        let res = client.try_check_event_type(&inspected, &vec![&env, inspector]);
+        assert_eq!(res, Err(Ok(Error::UnknownEventType)));
+
+        let res = client.try_deprecate_event_type(&admin, &inspected);
+        assert_eq!(res, Err(Ok(Error::InvalidInput)));
+    }
+
+    #[test]
+    fn test_only_admin_manages_types() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, admin) = setup(&env);
+        let attacker = Address::generate(&env);
+        let shipped = Symbol::new(&env, "shipped");
+
+        let res = client.try_register_event_type(&attacker, &shipped, &String::from_str(&env, ""), &vec![&env]);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+        client.register_event_type(&admin, &shipped, &String::from_str(&env, ""), &vec![&env]);
+        let res = client.try_deprecate_event_type(&attacker, &shipped);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+    }
+}
@@ -0,0 +1,125 @@
+use crate::types::{GeoBox, GeoPoint};
+
+// Coordinates are WGS 84 degrees scaled by 10^7 ("E7"), as used by most
+// GNSS receivers. Contracts can't use floating point, so distances are
+// computed in fixed point with an equirectangular approximation, which is
+// accurate to well under 1% over the few hundred kilometres a geofence
+// spans.
+
+pub const MAX_LAT_E7: i64 = 900_000_000;
+pub const MAX_LON_E7: i64 = 1_800_000_000;
+pub const MAX_ACCURACY_M: u32 = 100_000;
+
+/// Length of a degree of latitude, for a mean Earth radius of 6,371 km.
+const METRES_PER_DEGREE: i128 = 111_195;
+const COS_SCALE: i128 = 1_000_000;
+/// 180 degrees squared, in E7 units.
+const HALF_TURN_SQ_E14: i128 = 32_400 * 100_000_000_000_000;
+
+pub fn is_valid_point(point: &GeoPoint) -> bool {
+    (-MAX_LAT_E7..=MAX_LAT_E7).contains(&point.lat_e7)
+        && (-MAX_LON_E7..=MAX_LON_E7).contains(&point.lon_e7)
+        && point.accuracy_m <= MAX_ACCURACY_M
+}
+
+/// A box whose `min_lon_e7` is east of its `max_lon_e7` crosses the
+/// antimeridian.
+pub fn is_valid_box(area: &GeoBox) -> bool {
+    let lat = -MAX_LAT_E7..=MAX_LAT_E7;
+    let lon = -MAX_LON_E7..=MAX_LON_E7;
+    lat.contains(&area.min_lat_e7)
+        && lat.contains(&area.max_lat_e7)
+        && area.min_lat_e7 <= area.max_lat_e7
+        && lon.contains(&area.min_lon_e7)
+        && lon.contains(&area.max_lon_e7)
+}
+
+pub fn in_box(area: &GeoBox, point: &GeoPoint) -> bool {
+    if point.lat_e7 < area.min_lat_e7 || point.lat_e7 > area.max_lat_e7 {
+        return false;
+    }
+    if area.min_lon_e7 <= area.max_lon_e7 {
+        point.lon_e7 >= area.min_lon_e7 && point.lon_e7 <= area.max_lon_e7
+    } else {
+        point.lon_e7 >= area.min_lon_e7 || point.lon_e7 <= area.max_lon_e7
+    }
+}
+
+/// Bhaskara I's approximation of the cosine of a latitude, scaled by
+/// `COS_SCALE`. Within 0.2% over [-90, 90] degrees.
+fn cos_lat(lat_e7: i64) -> i128 {
+    let sq = (lat_e7 as i128) * (lat_e7 as i128);
+    (HALF_TURN_SQ_E14 - 4 * sq) * COS_SCALE / (HALF_TURN_SQ_E14 + sq)
+}
+
+/// Square of the approximate distance between two points, in mm².
+fn distance_sq_mm(a: &GeoPoint, b: &GeoPoint) -> i128 {
+    let dlat = (b.lat_e7 - a.lat_e7) as i128;
+    let mut dlon = (b.lon_e7 - a.lon_e7) as i128;
+    // Take the short way round
+    if dlon > MAX_LON_E7 as i128 {
+        dlon -= 2 * MAX_LON_E7 as i128;
+    } else if dlon < -(MAX_LON_E7 as i128) {
+        dlon += 2 * MAX_LON_E7 as i128;
+    }
+
+    // E7 units to mm: x 10^-7 degrees x METRES_PER_DEGREE x 10^3
+    let cos = cos_lat((a.lat_e7 + b.lat_e7) / 2);
+    let dy = dlat * METRES_PER_DEGREE / 10_000;
+    let dx = dlon * cos / COS_SCALE * METRES_PER_DEGREE / 10_000;
+    dx * dx + dy * dy
+}
+
+pub fn within_radius(center: &GeoPoint, point: &GeoPoint, radius_m: u32) -> bool {
+    let radius_mm = radius_m as i128 * 1_000;
+    distance_sq_mm(center, point) <= radius_mm * radius_mm
+}
+
+#[cfg(test)]
+mod test_geo {
+    use super::*;
+
+    fn point(lat: i64, lon: i64) -> GeoPoint {
+        GeoPoint { lat_e7: lat, lon_e7: lon, accuracy_m: 10 }
+    }
+
+    #[test]
+    fn test_point_ranges() {
+        assert!(is_valid_point(&point(MAX_LAT_E7, -MAX_LON_E7)));
+        assert!(!is_valid_point(&point(MAX_LAT_E7 + 1, 0)));
+        assert!(!is_valid_point(&point(0, MAX_LON_E7 + 1)));
+        let mut imprecise = point(0, 0);
+        imprecise.accuracy_m = MAX_ACCURACY_M + 1;
+        assert!(!is_valid_point(&imprecise));
+    }
+
+    #[test]
+    fn test_box_across_antimeridian() {
+        let area = GeoBox {
+            min_lat_e7: -100_000_000,
+            max_lat_e7: 100_000_000,
+            min_lon_e7: 1_790_000_000,
+            max_lon_e7: -1_790_000_000,
+        };
+        assert!(is_valid_box(&area));
+        assert!(in_box(&area, &point(0, 1_795_000_000)));
+        assert!(in_box(&area, &point(0, -1_795_000_000)));
+        assert!(!in_box(&area, &point(0, 0)));
+        assert!(!in_box(&area, &point(200_000_000, 1_795_000_000)));
+    }
+
+    #[test]
+    fn test_radius() {
+        // Rotterdam to Amsterdam Centraal is about 60.5 km
+        let rotterdam = point(519_000_000, 44_800_000);
+        let amsterdam = point(523_791_000, 49_003_000);
+        assert!(within_radius(&rotterdam, &amsterdam, 61_000));
+        assert!(!within_radius(&rotterdam, &amsterdam, 60_000));
+
+        // Either side of the antimeridian, about 22 km apart at the equator
+        let east = point(0, 1_799_000_000);
+        let west = point(0, -1_799_000_000);
+        assert!(within_radius(&east, &west, 23_000));
+        assert!(!within_radius(&east, &west, 21_000));
+    }
+}
@@ -0,0 +1,131 @@
+use crate::error::Error;
+
+// GS1 identifier checks on raw ASCII bytes, used for products that declare
+// an identifier scheme.
+
+pub const GTIN14_LEN: usize = 14;
+pub const SSCC_LEN: usize = 18;
+pub const MAX_SERIAL_LEN: usize = 20;
+
+/// SGTINs use the GS1 element string form `(01)<GTIN-14>(21)<serial>`.
+pub const SGTIN_GTIN_AI: &[u8] = b"(01)";
+pub const SGTIN_SERIAL_AI: &[u8] = b"(21)";
+
+fn all_digits(s: &[u8]) -> bool {
+    s.iter().all(u8::is_ascii_digit)
+}
+
+/// GS1 mod-10 check: weights 3 and 1 alternate leftwards from the digit
+/// next to the check digit.
+fn check_digit_valid(digits: &[u8]) -> bool {
+    let (body, check) = digits.split_at(digits.len() - 1);
+    let mut sum: u32 = 0;
+    for (i, d) in body.iter().rev().enumerate() {
+        let weight = if i % 2 == 0 { 3 } else { 1 };
+        sum += (d - b'0') as u32 * weight;
+    }
+    (10 - sum % 10) % 10 == (check[0] - b'0') as u32
+}
+
+/// Characters allowed in a GS1 serial number (the "CSET 82" set).
+fn is_serial_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric()
+        || matches!(
+            c,
+            b'!' | b'"' | b'%' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b'-' | b'.'
+                | b'/' | b':' | b';' | b'<' | b'=' | b'>' | b'?' | b'_'
+        )
+}
+
+/// Validate a GTIN-8, GTIN-12, GTIN-13 or GTIN-14.
+pub fn validate_gtin(gtin: &[u8]) -> Result<(), Error> {
+    if !matches!(gtin.len(), 8 | 12 | 13 | 14) || !all_digits(gtin) || !check_digit_valid(gtin) {
+        return Err(Error::InvalidIdentifier);
+    }
+    Ok(())
+}
+
+/// Validate an 18-digit Serial Shipping Container Code.
+pub fn validate_sscc(sscc: &[u8]) -> Result<(), Error> {
+    if sscc.len() != SSCC_LEN || !all_digits(sscc) || !check_digit_valid(sscc) {
+        return Err(Error::InvalidIdentifier);
+    }
+    Ok(())
+}
+
+pub fn validate_serial(serial: &[u8]) -> Result<(), Error> {
+    if serial.is_empty() || serial.len() > MAX_SERIAL_LEN || !serial.iter().all(|c| is_serial_char(*c)) {
+        return Err(Error::InvalidIdentifier);
+    }
+    Ok(())
+}
+
+/// Validate an SGTIN in element string form, returning its GTIN-14 and
+/// serial parts.
+pub fn parse_sgtin(sgtin: &[u8]) -> Result<(&[u8], &[u8]), Error> {
+    let gtin_end = SGTIN_GTIN_AI.len() + GTIN14_LEN;
+    let serial_start = gtin_end + SGTIN_SERIAL_AI.len();
+    if sgtin.len() <= serial_start
+        || !sgtin.starts_with(SGTIN_GTIN_AI)
+        || &sgtin[gtin_end..serial_start] != SGTIN_SERIAL_AI
+    {
+        return Err(Error::InvalidIdentifier);
+    }
+
+    let gtin = &sgtin[SGTIN_GTIN_AI.len()..gtin_end];
+    let serial = &sgtin[serial_start..];
+    validate_gtin(gtin)?;
+    validate_serial(serial)?;
+    Ok((gtin, serial))
+}
+
+/// Left-pad a valid GTIN-8, GTIN-12 or GTIN-13 with zeros to GTIN-14.
+pub fn to_gtin14(gtin: &[u8]) -> Result<[u8; GTIN14_LEN], Error> {
+    validate_gtin(gtin)?;
+    let mut padded = [b'0'; GTIN14_LEN];
+    padded[GTIN14_LEN - gtin.len()..].copy_from_slice(gtin);
+    Ok(padded)
+}
+
+#[cfg(test)]
+mod test_gs1 {
+    use super::*;
+
+    #[test]
+    fn test_gtin_lengths_and_check_digits() {
+        assert_eq!(validate_gtin(b"96385074"), Ok(()));
+        assert_eq!(validate_gtin(b"036000291452"), Ok(()));
+        assert_eq!(validate_gtin(b"4006381333931"), Ok(()));
+        assert_eq!(validate_gtin(b"09506000134352"), Ok(()));
+
+        assert_eq!(validate_gtin(b"4006381333932"), Err(Error::InvalidIdentifier));
+        assert_eq!(validate_gtin(b"400638133393"), Err(Error::InvalidIdentifier));
+        assert_eq!(validate_gtin(b"40063813339"), Err(Error::InvalidIdentifier));
+        assert_eq!(validate_gtin(b"40063813339A"), Err(Error::InvalidIdentifier));
+    }
+
+    #[test]
+    fn test_sscc() {
+        assert_eq!(validate_sscc(b"106141411234567897"), Ok(()));
+        assert_eq!(validate_sscc(b"106141411234567890"), Err(Error::InvalidIdentifier));
+        assert_eq!(validate_sscc(b"10614141123456789"), Err(Error::InvalidIdentifier));
+    }
+
+    #[test]
+    fn test_parse_sgtin() {
+        let (gtin, serial) = parse_sgtin(b"(01)09506000134352(21)ABC-123").unwrap();
+        assert_eq!(gtin, b"09506000134352");
+        assert_eq!(serial, b"ABC-123");
+
+        assert_eq!(parse_sgtin(b"(01)09506000134352(21)"), Err(Error::InvalidIdentifier));
+        assert_eq!(parse_sgtin(b"(01)9506000134352(21)ABC"), Err(Error::InvalidIdentifier));
+        assert_eq!(parse_sgtin(b"(01)09506000134353(21)ABC"), Err(Error::InvalidIdentifier));
+        assert_eq!(parse_sgtin(b"(01)09506000134352(21)A B"), Err(Error::InvalidIdentifier));
+    }
+
+    #[test]
+    fn test_to_gtin14() {
+        assert_eq!(&to_gtin14(b"96385074").unwrap(), b"00000096385074");
+        assert_eq!(&to_gtin14(b"09506000134352").unwrap(), b"09506000134352");
+    }
+}
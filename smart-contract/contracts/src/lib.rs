@@ -9,6 +9,11 @@ mod storage_contract;
 mod error;
 mod validation;
 mod validation_contract;
+mod gs1;
+mod geo;
+mod event_chain;
+mod event_sequence;
+mod telemetry;
 mod authorization;
 mod product_transfer;
 mod admin;
@@ -16,6 +21,9 @@ mod product_query;
 mod tracking;
 mod stats;
 mod event_query;
+mod certifier_registry;
+mod event_type_registry;
+mod device_registry;
 
 #[cfg(test)]
 mod test;
@@ -31,5 +39,8 @@ pub use product_query::*;
 pub use tracking::*;
 pub use stats::*;
 pub use event_query::*;
+pub use certifier_registry::*;
+pub use event_type_registry::*;
+pub use device_registry::*;
 pub use types::*;
 pub use error::*;
\ No newline at end of file
@@ -1,17 +1,18 @@
-use soroban_sdk::{contract, contractimpl, Address, Env, String};
+use soroban_sdk::{contract, contractimpl, Address, Env, String, Symbol};
 
 use crate::error::Error;
-use crate::types::{DataKey, Product, ProductStats};
+use crate::storage_contract::StorageContract;
+use crate::types::{DataKey, Product, ProductCursorPage, ProductPage, ProductStats};
 use crate::ProductRegistryContractClient;
 
 // ─── Storage helpers ─────────────────────────────────────────────────────────
 
 fn get_main_contract(env: &Env) -> Option<Address> {
-    env.storage().persistent().get(&DataKey::MainContract)
+    StorageContract::read_instance(env, &DataKey::MainContract)
 }
 
 fn set_main_contract(env: &Env, address: &Address) {
-    env.storage().persistent().set(&DataKey::MainContract, address);
+    StorageContract::write_instance(env, &DataKey::MainContract, address);
 }
 
 // ─── Contract ──────────────────────────────────────────────────────────────────
@@ -52,6 +53,71 @@ impl ProductQueryContract {
         Ok(pr_client.get_stats())
     }
 
+    /// List products from the global catalogue, following a cursor.
+    /// Delegates to ProductRegistryContract.
+    pub fn list_products(
+        env: Env,
+        cursor: u64,
+        limit: u64,
+        include_inactive: bool,
+    ) -> Result<ProductCursorPage, Error> {
+        let main_contract = get_main_contract(&env).ok_or(Error::NotInitialized)?;
+        let pr_client = ProductRegistryContractClient::new(&env, &main_contract);
+
+        Ok(pr_client.get_catalogue_page(&cursor, &limit, &include_inactive))
+    }
+
+    /// Get products in a category, optionally only the active ones.
+    /// Delegates to ProductRegistryContract.
+    pub fn get_products_by_category(
+        env: Env,
+        category: String,
+        offset: u64,
+        limit: u64,
+        active_only: bool,
+    ) -> Result<ProductPage, Error> {
+        let main_contract = get_main_contract(&env).ok_or(Error::NotInitialized)?;
+        let pr_client = ProductRegistryContractClient::new(&env, &main_contract);
+
+        Ok(pr_client.get_category_page(&category, &offset, &limit, &active_only))
+    }
+
+    /// Get products carrying a tag, optionally only the active ones.
+    /// Delegates to ProductRegistryContract.
+    pub fn get_products_by_tag(
+        env: Env,
+        tag: String,
+        offset: u64,
+        limit: u64,
+        active_only: bool,
+    ) -> Result<ProductPage, Error> {
+        let main_contract = get_main_contract(&env).ok_or(Error::NotInitialized)?;
+        let pr_client = ProductRegistryContractClient::new(&env, &main_contract);
+
+        Ok(pr_client.get_tag_page(&tag, &offset, &limit, &active_only))
+    }
+
+    /// Get total and active product counts for a category.
+    /// Delegates to ProductRegistryContract.
+    pub fn query_category_stats(env: Env, category: String) -> Result<ProductStats, Error> {
+        let main_contract = get_main_contract(&env).ok_or(Error::NotInitialized)?;
+        let pr_client = ProductRegistryContractClient::new(&env, &main_contract);
+
+        Ok(pr_client.get_category_stats(&category))
+    }
+
+    /// Resolve a partner identifier to a product ID.
+    /// Delegates to ProductRegistryContract.
+    pub fn resolve_alias(env: Env, namespace: Symbol, value: String) -> Result<String, Error> {
+        let main_contract = get_main_contract(&env).ok_or(Error::NotInitialized)?;
+        let pr_client = ProductRegistryContractClient::new(&env, &main_contract);
+
+        match pr_client.try_get_alias_target(&namespace, &value) {
+            Ok(Ok(product_id)) => Ok(product_id),
+            _ => Err(Error::ProductNotFound),
+        }
+    }
+
     /// Check if a product exists in the system.
     pub fn query_product_exists(env: Env, product_id: String) -> Result<bool, Error> {
         let main_contract = get_main_contract(&env).ok_or(Error::NotInitialized)?;
@@ -71,7 +137,7 @@ mod test_product_query {
     use crate::{
         AuthorizationContract, AuthorizationContractClient,
         ProductRegistryContract, ProductRegistryContractClient,
-        ProductConfig,
+        IdScheme, ProductConfig, ProductUpdate,
     };
 
     fn setup(env: &Env) -> (ProductRegistryContractClient, Address) {
@@ -101,6 +167,7 @@ mod test_product_query {
             owner,
             &ProductConfig {
                 id: product_id.clone(),
+                id_scheme: IdScheme::Custom,
                 name: String::from_str(env, "Test Product"),
                 description: String::from_str(env, "Description"),
                 origin_location: String::from_str(env, "Origin"),
@@ -114,6 +181,37 @@ mod test_product_query {
         product_id
     }
 
+    fn register_classified_product(
+        env: &Env,
+        client: &ProductRegistryContractClient,
+        owner: &Address,
+        id: &str,
+        category: &str,
+        tags: &[&str],
+    ) -> String {
+        let product_id = String::from_str(env, id);
+        let mut tag_list = Vec::new(env);
+        for tag in tags {
+            tag_list.push_back(String::from_str(env, tag));
+        }
+        client.register_product(
+            owner,
+            &ProductConfig {
+                id: product_id.clone(),
+                id_scheme: IdScheme::Custom,
+                name: String::from_str(env, "Test Product"),
+                description: String::from_str(env, "Description"),
+                origin_location: String::from_str(env, "Origin"),
+                category: String::from_str(env, category),
+                tags: tag_list,
+                certifications: Vec::new(env),
+                media_hashes: Vec::new(env),
+                custom: Map::new(env),
+            },
+        );
+        product_id
+    }
+
     #[test]
     fn test_query_product() {
         let env = Env::default();
@@ -148,6 +246,28 @@ mod test_product_query {
         assert_eq!(res, Err(Ok(Error::ProductNotFound)));
     }
 
+    #[test]
+    fn test_resolve_alias() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (pr_client, pr_id) = setup(&env);
+        let query_id = env.register_contract(None, super::ProductQueryContract);
+        let query_client = super::ProductQueryContractClient::new(&env, &query_id);
+        query_client.query_init(&pr_id);
+
+        let owner = Address::generate(&env);
+        let product_id = register_test_product(&env, &pr_client, &owner, "PROD1");
+        let erp = Symbol::new(&env, "erp");
+        let value = String::from_str(&env, "4500012345");
+        pr_client.add_alias(&owner, &product_id, &erp, &value);
+
+        assert_eq!(query_client.resolve_alias(&erp, &value), product_id);
+
+        let res = query_client.try_resolve_alias(&Symbol::new(&env, "sku"), &value);
+        assert_eq!(res, Err(Ok(Error::ProductNotFound)));
+    }
+
     #[test]
     fn test_query_stats() {
         let env = Env::default();
@@ -188,6 +308,175 @@ mod test_product_query {
         assert!(!query_client.query_product_exists(&fake_id));
     }
 
+    #[test]
+    fn test_list_products() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (pr_client, pr_id) = setup(&env);
+        let query_id = env.register_contract(None, super::ProductQueryContract);
+        let query_client = super::ProductQueryContractClient::new(&env, &query_id);
+        query_client.query_init(&pr_id);
+
+        let owner = Address::generate(&env);
+        register_test_product(&env, &pr_client, &owner, "PROD1");
+        register_test_product(&env, &pr_client, &owner, "PROD2");
+        register_test_product(&env, &pr_client, &owner, "PROD3");
+
+        let page = query_client.list_products(&0, &2, &true);
+        assert_eq!(page.products.len(), 2);
+        assert_eq!(page.products.get_unchecked(0).id, String::from_str(&env, "PROD1"));
+        assert_eq!(page.next_cursor, 2);
+        assert!(page.has_more);
+
+        let page = query_client.list_products(&page.next_cursor, &2, &true);
+        assert_eq!(page.products.len(), 1);
+        assert_eq!(page.products.get_unchecked(0).id, String::from_str(&env, "PROD3"));
+        assert!(!page.has_more);
+    }
+
+    #[test]
+    fn test_list_products_excludes_inactive() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (pr_client, pr_id) = setup(&env);
+        let query_id = env.register_contract(None, super::ProductQueryContract);
+        let query_client = super::ProductQueryContractClient::new(&env, &query_id);
+        query_client.query_init(&pr_id);
+
+        let owner = Address::generate(&env);
+        register_test_product(&env, &pr_client, &owner, "PROD1");
+        let inactive = register_test_product(&env, &pr_client, &owner, "PROD2");
+        register_test_product(&env, &pr_client, &owner, "PROD3");
+        pr_client.deactivate_product(&owner, &inactive, &String::from_str(&env, "Spoiled"));
+
+        let page = query_client.list_products(&0, &10, &false);
+        assert_eq!(page.products.len(), 2);
+        assert_eq!(page.next_cursor, 3);
+        assert!(!page.has_more);
+
+        let page = query_client.list_products(&0, &10, &true);
+        assert_eq!(page.products.len(), 3);
+
+        // Invalid limits and cursors past the end yield empty pages
+        assert!(query_client.list_products(&0, &0, &true).products.is_empty());
+        assert!(query_client.list_products(&10, &10, &true).products.is_empty());
+    }
+
+    #[test]
+    fn test_get_products_by_category() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (pr_client, pr_id) = setup(&env);
+        let query_id = env.register_contract(None, super::ProductQueryContract);
+        let query_client = super::ProductQueryContractClient::new(&env, &query_id);
+        query_client.query_init(&pr_id);
+
+        let owner = Address::generate(&env);
+        let coffee1 = register_classified_product(&env, &pr_client, &owner, "C1", "coffee", &[]);
+        let coffee2 = register_classified_product(&env, &pr_client, &owner, "C2", "coffee", &[]);
+        register_classified_product(&env, &pr_client, &owner, "K1", "cocoa", &[]);
+
+        let coffee = String::from_str(&env, "coffee");
+        let page = query_client.get_products_by_category(&coffee, &0, &10, &false);
+        assert_eq!(page.total_count, 2);
+        assert_eq!(page.products.get_unchecked(0).id, coffee1);
+        assert_eq!(page.products.get_unchecked(1).id, coffee2);
+
+        pr_client.deactivate_product(&owner, &coffee1, &String::from_str(&env, "Sold out"));
+
+        let page = query_client.get_products_by_category(&coffee, &0, &10, &true);
+        assert_eq!(page.total_count, 1);
+        assert_eq!(page.products.get_unchecked(0).id, coffee2);
+
+        let stats = query_client.query_category_stats(&coffee);
+        assert_eq!(stats.total_products, 2);
+        assert_eq!(stats.active_products, 1);
+
+        pr_client.reactivate_product(&owner, &coffee1, &String::from_str(&env, ""));
+        assert_eq!(query_client.query_category_stats(&coffee).active_products, 2);
+
+        let unknown = query_client.query_category_stats(&String::from_str(&env, "electronics"));
+        assert_eq!(unknown.total_products, 0);
+    }
+
+    #[test]
+    fn test_get_products_by_tag() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (pr_client, pr_id) = setup(&env);
+        let query_id = env.register_contract(None, super::ProductQueryContract);
+        let query_client = super::ProductQueryContractClient::new(&env, &query_id);
+        query_client.query_init(&pr_id);
+
+        let owner = Address::generate(&env);
+        register_classified_product(&env, &pr_client, &owner, "P1", "coffee", &["organic", "fairtrade"]);
+        let p2 = register_classified_product(&env, &pr_client, &owner, "P2", "cocoa", &["organic"]);
+        register_classified_product(&env, &pr_client, &owner, "P3", "cocoa", &["fairtrade"]);
+
+        let organic = String::from_str(&env, "organic");
+        let page = query_client.get_products_by_tag(&organic, &0, &1, &false);
+        assert_eq!(page.total_count, 2);
+        assert_eq!(page.products.len(), 1);
+        assert!(page.has_more);
+
+        pr_client.deactivate_product(&owner, &p2, &String::from_str(&env, "Recalled"));
+
+        let page = query_client.get_products_by_tag(&organic, &0, &10, &true);
+        assert_eq!(page.total_count, 1);
+        assert_eq!(page.products.get_unchecked(0).id, String::from_str(&env, "P1"));
+    }
+
+    #[test]
+    fn test_indexes_follow_product_update() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (pr_client, pr_id) = setup(&env);
+        let query_id = env.register_contract(None, super::ProductQueryContract);
+        let query_client = super::ProductQueryContractClient::new(&env, &query_id);
+        query_client.query_init(&pr_id);
+
+        let owner = Address::generate(&env);
+        let id = register_classified_product(&env, &pr_client, &owner, "P1", "coffee", &["raw"]);
+
+        let product = pr_client.get_product(&id);
+        let mut tags = Vec::new(&env);
+        tags.push_back(String::from_str(&env, "roasted"));
+        pr_client.update_product(
+            &owner,
+            &id,
+            &ProductUpdate {
+                name: product.name,
+                description: product.description,
+                category: String::from_str(&env, "roasted-coffee"),
+                tags,
+                certifications: product.certifications,
+                media_hashes: product.media_hashes,
+                custom: product.custom,
+            },
+        );
+
+        let coffee = String::from_str(&env, "coffee");
+        assert_eq!(query_client.query_category_stats(&coffee).total_products, 0);
+        let page = query_client.get_products_by_category(
+            &String::from_str(&env, "roasted-coffee"),
+            &0,
+            &10,
+            &true,
+        );
+        assert_eq!(page.products.get_unchecked(0).id, id);
+
+        let raw = query_client.get_products_by_tag(&String::from_str(&env, "raw"), &0, &10, &false);
+        assert_eq!(raw.total_count, 0);
+        let roasted =
+            query_client.get_products_by_tag(&String::from_str(&env, "roasted"), &0, &10, &false);
+        assert_eq!(roasted.total_count, 1);
+    }
+
     #[test]
     fn test_init_already_initialized_fails() {
         let env = Env::default();
@@ -1,27 +1,58 @@
-use soroban_sdk::{contract, contractimpl, Address, Env, String, Symbol, Vec};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Map, String, Symbol, Vec};
 
 use crate::error::Error;
-use crate::AuthorizationContractClient;
-use crate::types::{DeactInfo, Origin, Product, ProductConfig, ProductStats};
+use crate::storage_contract::StorageContract;
+use crate::gs1;
+use crate::{AuthorizationContractClient, CertifierRegistryContractClient, TrackingContractClient};
+use crate::types::{
+    Certification, DeactInfo, IdScheme, LifecycleAction, LifecycleEntry, LifecyclePage, Origin,
+    Product, ProductAlias, ProductConfig, ProductCursorPage, ProductPage, ProductRevision,
+    ProductStats, ProductStatus, ProductUpdate, Recall, RecallSeverity,
+};
 use crate::storage;
 use crate::validation_contract::ValidationContract;
 
 // ─── Storage helpers for trusted transfer contract ───────────────────────────
 
 fn get_transfer_contract(env: &Env) -> Option<Address> {
-    env.storage().persistent().get(&crate::types::DataKey::TransferContract)
+    StorageContract::read_instance(env, &crate::types::DataKey::TransferContract)
 }
 
 fn set_transfer_contract(env: &Env, address: &Address) {
-    env.storage().persistent().set(&crate::types::DataKey::TransferContract, address);
+    StorageContract::write_instance(env, &crate::types::DataKey::TransferContract, address);
 }
 
 fn get_auth_contract(env: &Env) -> Option<Address> {
-    env.storage().persistent().get(&crate::types::DataKey::AuthContract)
+    StorageContract::read_instance(env, &crate::types::DataKey::AuthContract)
 }
 
 fn set_auth_contract(env: &Env, address: &Address) {
-    env.storage().persistent().set(&crate::types::DataKey::AuthContract, address);
+    StorageContract::write_instance(env, &crate::types::DataKey::AuthContract, address);
+}
+
+fn get_tracking_contract(env: &Env) -> Option<Address> {
+    StorageContract::read_instance(env, &crate::types::DataKey::TrackingContract)
+}
+
+fn set_tracking_contract(env: &Env, address: &Address) {
+    StorageContract::write_instance(env, &crate::types::DataKey::TrackingContract, address);
+}
+
+fn require_tracking_contract(env: &Env, caller: &Address) -> Result<(), Error> {
+    let trusted = get_tracking_contract(env).ok_or(Error::NotInitialized)?;
+    caller.require_auth();
+    if *caller != trusted {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn get_certifier_registry(env: &Env) -> Option<Address> {
+    StorageContract::read_instance(env, &crate::types::DataKey::CertifierRegistry)
+}
+
+fn set_certifier_registry(env: &Env, address: &Address) {
+    StorageContract::write_instance(env, &crate::types::DataKey::CertifierRegistry, address);
 }
 
 fn require_transfer_contract(env: &Env, caller: &Address) -> Result<(), Error> {
@@ -43,6 +74,15 @@ fn write_product(env: &Env, product: &Product) {
     storage::put_product(env, product);
 }
 
+fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
+    let admin = storage::get_admin(env).ok_or(Error::NotInitialized)?;
+    caller.require_auth();
+    if &admin != caller {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
 fn require_owner(product: &Product, caller: &Address) -> Result<(), Error> {
     caller.require_auth();
     if &product.owner != caller {
@@ -51,6 +91,337 @@ fn require_owner(product: &Product, caller: &Address) -> Result<(), Error> {
     Ok(())
 }
 
+/// Build a fresh, active product record from a validated config.
+fn build_product(env: &Env, owner: &Address, config: ProductConfig) -> Product {
+    Product {
+        id: config.id,
+        id_scheme: config.id_scheme,
+        name: config.name,
+        description: config.description,
+        origin: Origin {
+            location: config.origin_location,
+        },
+        owner: owner.clone(),
+        created_at: env.ledger().timestamp(),
+        active: true,
+        status: ProductStatus::Registered,
+        category: config.category,
+        tags: config.tags,
+        certifications: config.certifications,
+        media_hashes: config.media_hashes,
+        custom: config.custom,
+        deactivation_info: Vec::new(env),
+    }
+}
+
+/// Persist a newly registered product and register its owner with the
+/// AuthorizationContract. Does not touch the total and active counters.
+fn store_new_product(env: &Env, product: &Product) -> Result<(), Error> {
+    write_product(env, product);
+    let registered = storage::get_status_count(env, product.status) + 1;
+    storage::set_status_count(env, product.status, registered);
+    storage::put_product_event_ids(env, &product.id, &Vec::new(env));
+    storage::set_auth(env, &product.id, &product.owner, true);
+    storage::add_owner_product(env, &product.owner, &product.id);
+    storage::append_product_index(env, &product.id);
+    index_attributes(env, product, false);
+    index_attributes(env, product, true);
+
+    let auth_contract = get_auth_contract(env).ok_or(Error::NotInitialized)?;
+    let auth_client = AuthorizationContractClient::new(env, &auth_contract);
+    let self_address = env.current_contract_address();
+    auth_client.init_product_owner(&self_address, &product.id, &product.owner);
+
+    Ok(())
+}
+
+/// Validate a batch of configs for new products: size limits, field
+/// validation, and duplicate IDs within the batch or against storage.
+fn validate_new_configs(env: &Env, configs: &Vec<ProductConfig>) -> Result<(), Error> {
+    ValidationContract::validate_batch_size(configs.len())?;
+
+    let mut seen: Map<String, bool> = Map::new(env);
+    for i in 0..configs.len() {
+        let config = configs.get_unchecked(i);
+        ValidationContract::validate_product_config(&config)?;
+
+        if seen.contains_key(config.id.clone()) {
+            return Err(Error::DuplicateProductIdInBatch);
+        }
+        if storage::has_product(env, &config.id) {
+            return Err(Error::ProductAlreadyExists);
+        }
+        seen.set(config.id.clone(), true);
+    }
+
+    Ok(())
+}
+
+/// Create products from configs already checked by `validate_new_configs`,
+/// emitting `product_registered` for each and updating the global counters
+/// once.
+fn register_new_products(
+    env: &Env,
+    owner: &Address,
+    configs: &Vec<ProductConfig>,
+) -> Result<Vec<Product>, Error> {
+    let mut products = Vec::new(env);
+    for i in 0..configs.len() {
+        let product = build_product(env, owner, configs.get_unchecked(i));
+        store_new_product(env, &product)?;
+
+        env.events().publish(
+            (Symbol::new(env, "product_registered"), product.id.clone()),
+            product.clone(),
+        );
+
+        products.push_back(product);
+    }
+
+    let count = products.len() as u64;
+    let total = storage::get_total_products(env) + count;
+    storage::set_total_products(env, total);
+
+    let active = storage::get_active_products(env) + count;
+    storage::set_active_products(env, active);
+
+    Ok(products)
+}
+
+/// Check that a product can be used as the input of an assembly, split or
+/// merge, returning it. Callers check who may use it.
+fn read_usable_input(env: &Env, product_id: &String) -> Result<Product, Error> {
+    let product = read_product(env, product_id)?;
+    if is_recalled(env, product_id) {
+        return Err(Error::ProductRecalled);
+    }
+    if is_used_up(env, product_id) {
+        return Err(Error::ProductConsumed);
+    }
+    if !product.active {
+        return Err(Error::ProductDeactivated);
+    }
+    Ok(product)
+}
+
+/// Split and merge register the derived products to the caller, so only
+/// the owner of a source may split or merge it.
+fn read_owned_input(env: &Env, product_id: &String, owner: &Address) -> Result<Product, Error> {
+    let product = read_usable_input(env, product_id)?;
+    if &product.owner != owner {
+        return Err(Error::Unauthorized);
+    }
+    Ok(product)
+}
+
+/// A product is used up once it has been assembled into a parent or split
+/// or merged into derived products. Used-up products can't be reactivated.
+fn is_used_up(env: &Env, product_id: &String) -> bool {
+    storage::get_product_parent(env, product_id).is_some()
+        || !storage::get_derived_products(env, product_id).is_empty()
+}
+
+fn is_recalled(env: &Env, product_id: &String) -> bool {
+    storage::get_product_recall(env, product_id).is_some()
+}
+
+/// Take an active product out of circulation: mark it inactive with the
+/// given reason, drop it from the active-only indexes and decrement the
+/// active counter. Callers check that the product is active beforehand.
+fn retire_product(env: &Env, product: &mut Product, by: &Address, reason: &String) {
+    product.active = false;
+    let mut info = Vec::new(env);
+    info.push_back(DeactInfo {
+        reason: reason.clone(),
+        deactivated_at: env.ledger().timestamp(),
+        deactivated_by: by.clone(),
+    });
+    product.deactivation_info = info;
+
+    write_product(env, product);
+    unindex_attributes(env, product, true);
+    log_lifecycle(env, &product.id, LifecycleAction::Deactivated, by, reason);
+
+    // Decrement active counter
+    let active = storage::get_active_products(env).saturating_sub(1);
+    storage::set_active_products(env, active);
+}
+
+fn log_lifecycle(
+    env: &Env,
+    product_id: &String,
+    action: LifecycleAction,
+    by: &Address,
+    reason: &String,
+) {
+    storage::append_lifecycle_entry(
+        env,
+        product_id,
+        &LifecycleEntry {
+            action,
+            reason: reason.clone(),
+            at: env.ledger().timestamp(),
+            by: by.clone(),
+        },
+    );
+}
+
+/// Transitions allowed when the admin hasn't configured one. Products move
+/// forward through production, storage and transit, and anything but a
+/// destroyed product can be destroyed.
+fn default_transition_allowed(from: ProductStatus, to: ProductStatus) -> bool {
+    use ProductStatus::*;
+    match (from, to) {
+        (Destroyed, _) => false,
+        (_, Destroyed) => true,
+        (Registered, InProduction | InStorage | InTransit) => true,
+        (InProduction, InStorage | InTransit) => true,
+        (InStorage, InProduction | InTransit | Delivered | Sold) => true,
+        (InTransit, InStorage | Delivered) => true,
+        (Delivered, InStorage | InTransit | Sold) => true,
+        _ => false,
+    }
+}
+
+/// Products only become `Recalled` through `recall_product`, and since the
+/// recall stays on record they can only leave it to be destroyed. No
+/// configuration can allow any other transition into or out of it.
+fn is_transition_allowed(env: &Env, from: ProductStatus, to: ProductStatus) -> bool {
+    if to == ProductStatus::Recalled
+        || (from == ProductStatus::Recalled && to != ProductStatus::Destroyed)
+    {
+        return false;
+    }
+    storage::get_status_transition(env, from, to)
+        .unwrap_or_else(|| default_transition_allowed(from, to))
+}
+
+/// Status an event type moves a product to when the admin hasn't mapped it.
+fn default_event_status(env: &Env, event_type: &Symbol) -> Option<ProductStatus> {
+    let defaults = [
+        ("produced", ProductStatus::InProduction),
+        ("shipped", ProductStatus::InTransit),
+        ("received", ProductStatus::InStorage),
+        ("stored", ProductStatus::InStorage),
+        ("delivered", ProductStatus::Delivered),
+        ("sold", ProductStatus::Sold),
+        ("destroyed", ProductStatus::Destroyed),
+    ];
+    for (name, status) in defaults.iter() {
+        if *event_type == Symbol::new(env, name) {
+            return Some(*status);
+        }
+    }
+    None
+}
+
+fn event_status(env: &Env, event_type: &Symbol) -> Option<ProductStatus> {
+    storage::get_event_status(env, event_type)
+        .unwrap_or_else(|| default_event_status(env, event_type))
+}
+
+/// Move a product to a new status if the transition is allowed. Moving to
+/// the status it already has is a no-op.
+fn change_status(env: &Env, product: &mut Product, to: ProductStatus, by: &Address) -> Result<(), Error> {
+    if product.status == to {
+        return Ok(());
+    }
+    if !is_transition_allowed(env, product.status, to) {
+        return Err(Error::InvalidStatusTransition);
+    }
+    move_status(env, product, to, by);
+    Ok(())
+}
+
+/// Set a product's status without checking the transition, keeping the
+/// per-status counters in step.
+fn move_status(env: &Env, product: &mut Product, to: ProductStatus, by: &Address) {
+    let from = product.status;
+    storage::set_status_count(env, from, storage::get_status_count(env, from).saturating_sub(1));
+    storage::set_status_count(env, to, storage::get_status_count(env, to) + 1);
+
+    product.status = to;
+    write_product(env, product);
+
+    env.events().publish(
+        (Symbol::new(env, "status_changed"), product.id.clone()),
+        (from, to, by.clone()),
+    );
+}
+
+/// Owners may always act on a product; anyone else must be an authorized
+/// actor in the AuthorizationContract. The caller's auth is not required here.
+fn is_owner_or_authorized(env: &Env, product: &Product, caller: &Address) -> Result<bool, Error> {
+    if &product.owner == caller {
+        return Ok(true);
+    }
+
+    let auth_contract = get_auth_contract(env).ok_or(Error::NotInitialized)?;
+    let auth_client = AuthorizationContractClient::new(env, &auth_contract);
+    Ok(auth_client.is_authorized(&product.id, caller))
+}
+
+/// Add a product to its category and tag indexes. `active_only` selects
+/// the lists that hold only active products.
+fn index_attributes(env: &Env, product: &Product, active_only: bool) {
+    storage::add_category_product(env, &product.category, active_only, &product.id);
+    for i in 0..product.tags.len() {
+        storage::add_tag_product(env, &product.tags.get_unchecked(i), active_only, &product.id);
+    }
+}
+
+fn unindex_attributes(env: &Env, product: &Product, active_only: bool) {
+    storage::remove_category_product(env, &product.category, active_only, &product.id);
+    for i in 0..product.tags.len() {
+        storage::remove_tag_product(env, &product.tags.get_unchecked(i), active_only, &product.id);
+    }
+}
+
+/// Load the products for a page of IDs taken from one of the indexes.
+fn load_product_page(env: &Env, ids: Vec<String>, offset: u64, total_count: u64) -> ProductPage {
+    let mut products = Vec::new(env);
+    for i in 0..ids.len() {
+        if let Some(product) = storage::get_product(env, &ids.get_unchecked(i)) {
+            products.push_back(product);
+        }
+    }
+
+    let has_more = offset + (ids.len() as u64) < total_count;
+
+    ProductPage {
+        products,
+        total_count,
+        has_more,
+    }
+}
+
+/// Names of the fields that differ between a product and a proposed update.
+fn changed_fields(env: &Env, product: &Product, update: &ProductUpdate) -> Vec<Symbol> {
+    let mut changed = Vec::new(env);
+    if product.name != update.name {
+        changed.push_back(Symbol::new(env, "name"));
+    }
+    if product.description != update.description {
+        changed.push_back(Symbol::new(env, "description"));
+    }
+    if product.category != update.category {
+        changed.push_back(Symbol::new(env, "category"));
+    }
+    if product.tags != update.tags {
+        changed.push_back(Symbol::new(env, "tags"));
+    }
+    if product.certifications != update.certifications {
+        changed.push_back(Symbol::new(env, "certifications"));
+    }
+    if product.media_hashes != update.media_hashes {
+        changed.push_back(Symbol::new(env, "media_hashes"));
+    }
+    if product.custom != update.custom {
+        changed.push_back(Symbol::new(env, "custom"));
+    }
+    changed
+}
+
 // ─── Contract ────────────────────────────────────────────────────────────────
 
 #[contract]
@@ -80,33 +451,8 @@ impl ProductRegistryContract {
             return Err(Error::ProductAlreadyExists);
         }
 
-        // --- Build product ---
-        let product = Product {
-            id: config.id.clone(),
-            name: config.name,
-            description: config.description,
-            origin: Origin {
-                location: config.origin_location,
-            },
-            owner: owner.clone(),
-            created_at: env.ledger().timestamp(),
-            active: true,
-            category: config.category,
-            tags: config.tags,
-            certifications: config.certifications,
-            media_hashes: config.media_hashes,
-            custom: config.custom,
-            deactivation_info: Vec::new(&env),
-        };
-
-        write_product(&env, &product);
-        storage::put_product_event_ids(&env, &config.id, &Vec::new(&env));
-        storage::set_auth(&env, &config.id, &owner, true);
-
-        let auth_contract = get_auth_contract(&env).ok_or(Error::NotInitialized)?;
-        let auth_client = AuthorizationContractClient::new(&env, &auth_contract);
-        let self_address = env.current_contract_address();
-        auth_client.init_product_owner(&self_address, &config.id, &owner);
+        let product = build_product(&env, &owner, config);
+        store_new_product(&env, &product)?;
 
         // Update global counters
         let total = storage::get_total_products(&env) + 1;
@@ -116,13 +462,29 @@ impl ProductRegistryContract {
         storage::set_active_products(&env, active);
 
         env.events().publish(
-            (Symbol::new(&env, "product_registered"), config.id.clone()),
+            (Symbol::new(&env, "product_registered"), product.id.clone()),
             product.clone(),
         );
 
         Ok(product)
     }
 
+    /// Register several products for the same owner in one call.
+    ///
+    /// Every config is validated and checked for duplicates (within the batch
+    /// and against storage) before anything is written, so the batch is
+    /// all-or-nothing. Global counters are updated once for the whole batch.
+    pub fn register_products_batch(
+        env: Env,
+        owner: Address,
+        configs: Vec<ProductConfig>,
+    ) -> Result<Vec<Product>, Error> {
+        owner.require_auth();
+
+        validate_new_configs(&env, &configs)?;
+        register_new_products(&env, &owner, &configs)
+    }
+
     pub fn configure_auth_contract(env: Env, auth_contract: Address) -> Result<(), Error> {
         match get_auth_contract(&env) {
             None => {
@@ -150,6 +512,63 @@ impl ProductRegistryContract {
         }
     }
 
+    /// Configure which contract may report tracking events through
+    /// `record_event_status`. Admin only.
+    ///
+    /// One-time set (or idempotent if set to the same address).
+    pub fn configure_tracking_contract(
+        env: Env,
+        admin: Address,
+        tracking_contract: Address,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        match get_tracking_contract(&env) {
+            None => {
+                set_tracking_contract(&env, &tracking_contract);
+                Ok(())
+            }
+            Some(existing) if existing == tracking_contract => Ok(()),
+            Some(_) => Err(Error::AlreadyInitialized),
+        }
+    }
+
+    /// Configure the CertifierRegistryContract that certification issuers
+    /// must be accredited in. Admin only. No certifications are accepted
+    /// until one is configured.
+    ///
+    /// One-time set (or idempotent if set to the same address).
+    pub fn configure_certifier_registry(
+        env: Env,
+        admin: Address,
+        certifier_registry: Address,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        match get_certifier_registry(&env) {
+            None => {
+                set_certifier_registry(&env, &certifier_registry);
+                Ok(())
+            }
+            Some(existing) if existing == certifier_registry => Ok(()),
+            Some(_) => Err(Error::AlreadyInitialized),
+        }
+    }
+
+    /// Configure the registry admin, who may recall any product and
+    /// configure the status state machine.
+    ///
+    /// One-time set (or idempotent if set to the same address).
+    pub fn configure_admin(env: Env, admin: Address) -> Result<(), Error> {
+        admin.require_auth();
+        match storage::get_admin(&env) {
+            None => {
+                storage::set_admin(&env, &admin);
+                Ok(())
+            }
+            Some(existing) if existing == admin => Ok(()),
+            Some(_) => Err(Error::AlreadyInitialized),
+        }
+    }
+
     /// Update a product's `owner` field.
     ///
     /// This must only be called by the configured `ProductTransferContract`.
@@ -165,6 +584,10 @@ impl ProductRegistryContract {
         if !product.active {
             return Err(Error::ProductDeactivated);
         }
+
+        storage::remove_owner_product(&env, &product.owner, &product_id);
+        storage::add_owner_product(&env, &new_owner, &product_id);
+
         product.owner = new_owner;
         write_product(&env, &product);
 
@@ -172,20 +595,20 @@ impl ProductRegistryContract {
     }
 
     // ═══════════════════════════════════════════════════════════════════════
-    // PRODUCT LIFECYCLE — DEACTIVATION & REACTIVATION
+    // PRODUCT UPDATES & REVISION HISTORY
     // ═══════════════════════════════════════════════════════════════════════
 
-    /// Deactivate a product.
+    /// Update a product's editable details.
     ///
-    /// Only the product owner can deactivate. A reason must be provided.
-    /// Deactivation prevents new tracking events and decrements the active
-    /// product counter.
-    pub fn deactivate_product(
+    /// Only the owner can update an active product. The current state is
+    /// archived as a new revision before the update is applied, and a
+    /// `product_updated` event lists the fields that changed.
+    pub fn update_product(
         env: Env,
         owner: Address,
         product_id: String,
-        reason: String,
-    ) -> Result<(), Error> {
+        update: ProductUpdate,
+    ) -> Result<Product, Error> {
         let mut product = read_product(&env, &product_id)?;
         require_owner(&product, &owner)?;
 
@@ -193,71 +616,996 @@ impl ProductRegistryContract {
             return Err(Error::ProductDeactivated);
         }
 
-        ValidationContract::validate_deactivation_reason(&reason)?;
+        ValidationContract::validate_product_update(&update)?;
 
-        product.active = false;
-        let mut info = Vec::new(&env);
-        info.push_back(DeactInfo {
-            reason: reason.clone(),
-            deactivated_at: env.ledger().timestamp(),
-            deactivated_by: owner.clone(),
-        });
-        product.deactivation_info = info;
+        let changed = changed_fields(&env, &product, &update);
+        if changed.is_empty() {
+            return Err(Error::NoChangesInUpdate);
+        }
+
+        let revision = storage::get_product_revision_count(&env, &product_id);
+        storage::put_product_revision(
+            &env,
+            &ProductRevision {
+                revision,
+                product: product.clone(),
+                replaced_at: env.ledger().timestamp(),
+                replaced_by: owner.clone(),
+            },
+        );
+
+        let reindex = product.category != update.category || product.tags != update.tags;
+        if reindex {
+            unindex_attributes(&env, &product, false);
+            unindex_attributes(&env, &product, true);
+        }
+
+        product.name = update.name;
+        product.description = update.description;
+        product.category = update.category;
+        product.tags = update.tags;
+        product.certifications = update.certifications;
+        product.media_hashes = update.media_hashes;
+        product.custom = update.custom;
 
         write_product(&env, &product);
 
-        // Decrement active counter
-        let active = storage::get_active_products(&env).saturating_sub(1);
-        storage::set_active_products(&env, active);
+        if reindex {
+            index_attributes(&env, &product, false);
+            index_attributes(&env, &product, true);
+        }
 
         env.events().publish(
-            (Symbol::new(&env, "product_deactivated"), product_id.clone()),
-            (owner, reason),
+            (Symbol::new(&env, "product_updated"), product_id.clone()),
+            (owner, revision + 1, changed),
         );
 
-        Ok(())
+        Ok(product)
     }
 
-    /// Reactivate a previously deactivated product.
+    /// Get an archived revision of a product.
     ///
-    /// Only the product owner can reactivate. Clears deactivation info
-    /// and increments the active product counter.
-    pub fn reactivate_product(
+    /// Revision 0 is the product as registered; the current state is not
+    /// archived until it is itself replaced.
+    pub fn get_product_revision(
+        env: Env,
+        id: String,
+        revision: u32,
+    ) -> Result<ProductRevision, Error> {
+        let _ = read_product(&env, &id)?;
+        storage::get_product_revision(&env, &id, revision).ok_or(Error::RevisionNotFound)
+    }
+
+    /// Get every archived revision of a product, oldest first.
+    pub fn get_product_history(env: Env, id: String) -> Result<Vec<ProductRevision>, Error> {
+        let _ = read_product(&env, &id)?;
+
+        let count = storage::get_product_revision_count(&env, &id);
+        let mut history = Vec::new(&env);
+        for rev in 0..count {
+            if let Some(revision) = storage::get_product_revision(&env, &id, rev) {
+                history.push_back(revision);
+            }
+        }
+
+        Ok(history)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // PRODUCT COMPOSITION (BILL OF MATERIALS)
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Assemble components into a parent product.
+    ///
+    /// The caller must own the parent and own or be authorized on every
+    /// component. All components are checked before any is linked; each is
+    /// then recorded under the parent and consumed (deactivated for good).
+    pub fn assemble_product(
         env: Env,
         owner: Address,
-        product_id: String,
+        parent_id: String,
+        component_ids: Vec<String>,
     ) -> Result<(), Error> {
-        let mut product = read_product(&env, &product_id)?;
-        require_owner(&product, &owner)?;
+        let parent = read_product(&env, &parent_id)?;
+        require_owner(&parent, &owner)?;
 
-        if product.active {
-            return Err(Error::ProductAlreadyActive);
+        if !parent.active {
+            return Err(Error::ProductDeactivated);
         }
 
-        product.active = true;
-        product.deactivation_info = Vec::new(&env);
+        ValidationContract::validate_batch_size(component_ids.len())?;
 
-        write_product(&env, &product);
+        // --- Validate every component up front ---
+        let mut seen: Map<String, bool> = Map::new(&env);
+        let mut components = Vec::new(&env);
+        for i in 0..component_ids.len() {
+            let component_id = component_ids.get_unchecked(i);
 
-        // Increment active counter
-        let active = storage::get_active_products(&env) + 1;
-        storage::set_active_products(&env, active);
+            if component_id == parent_id {
+                return Err(Error::InvalidComponent);
+            }
+            if seen.contains_key(component_id.clone()) {
+                return Err(Error::DuplicateProductIdInBatch);
+            }
+            seen.set(component_id.clone(), true);
+
+            let component = read_usable_input(&env, &component_id)?;
+            if !is_owner_or_authorized(&env, &component, &owner)? {
+                return Err(Error::Unauthorized);
+            }
+            components.push_back(component);
+        }
+
+        // --- Link and consume ---
+        let mut linked = storage::get_product_components(&env, &parent_id);
+        let reason = String::from_str(&env, "consumed");
+        for i in 0..components.len() {
+            let mut component = components.get_unchecked(i);
+            linked.push_back(component.id.clone());
+            storage::set_product_parent(&env, &component.id, &parent_id);
+            retire_product(&env, &mut component, &owner, &reason);
+        }
+        storage::put_product_components(&env, &parent_id, &linked);
 
         env.events().publish(
-            (Symbol::new(&env, "product_reactivated"), product_id.clone()),
-            owner,
+            (Symbol::new(&env, "product_assembled"), parent_id),
+            (owner, component_ids),
         );
 
         Ok(())
     }
 
+    /// Get the IDs of the components assembled directly into a product.
+    pub fn get_components(env: Env, id: String) -> Result<Vec<String>, Error> {
+        let _ = read_product(&env, &id)?;
+        Ok(storage::get_product_components(&env, &id))
+    }
+
+    /// Get the product a component was assembled into, if any.
+    pub fn get_parent(env: Env, id: String) -> Result<Option<String>, Error> {
+        let _ = read_product(&env, &id)?;
+        Ok(storage::get_product_parent(&env, &id))
+    }
+
+    /// Get a product and every product it was made from, breadth first:
+    /// assembled components as well as split/merge sources, transitively.
+    ///
+    /// These are the product IDs whose tracking events make up the full
+    /// provenance of a finished good.
+    pub fn get_provenance(env: Env, id: String) -> Result<Vec<String>, Error> {
+        let _ = read_product(&env, &id)?;
+
+        let mut seen: Map<String, bool> = Map::new(&env);
+        let mut provenance = Vec::new(&env);
+        seen.set(id.clone(), true);
+        provenance.push_back(id);
+
+        let mut i = 0;
+        while i < provenance.len() {
+            let current = provenance.get_unchecked(i);
+            let mut inputs = storage::get_product_components(&env, &current);
+            inputs.append(&storage::get_product_sources(&env, &current));
+
+            for j in 0..inputs.len() {
+                let input = inputs.get_unchecked(j);
+                if !seen.contains_key(input.clone()) {
+                    seen.set(input.clone(), true);
+                    provenance.push_back(input);
+                }
+            }
+            i += 1;
+        }
+
+        Ok(provenance)
+    }
+
     // ═══════════════════════════════════════════════════════════════════════
-    // PRODUCT QUERIES
+    // LOT SPLITTING & MERGING
     // ═══════════════════════════════════════════════════════════════════════
 
-    /// Get a product by its string ID.
-    pub fn get_product(env: Env, id: String) -> Result<Product, Error> {
-        read_product(&env, &id)
+    /// Split one product into several derived products.
+    ///
+    /// Only the source's owner may split it. The derived products are
+    /// registered to `owner` and linked back to the source, which is then
+    /// retired. Emits `product_split`.
+    pub fn split_product(
+        env: Env,
+        owner: Address,
+        source_id: String,
+        configs: Vec<ProductConfig>,
+    ) -> Result<Vec<Product>, Error> {
+        owner.require_auth();
+
+        let mut source = read_owned_input(&env, &source_id, &owner)?;
+        validate_new_configs(&env, &configs)?;
+
+        let products = register_new_products(&env, &owner, &configs)?;
+
+        let mut sources = Vec::new(&env);
+        sources.push_back(source_id.clone());
+        let mut derived = Vec::new(&env);
+        for i in 0..products.len() {
+            let product_id = products.get_unchecked(i).id;
+            storage::put_product_sources(&env, &product_id, &sources);
+            derived.push_back(product_id);
+        }
+        storage::put_derived_products(&env, &source_id, &derived);
+
+        retire_product(&env, &mut source, &owner, &String::from_str(&env, "split"));
+
+        env.events().publish(
+            (Symbol::new(&env, "product_split"), source_id),
+            (owner, derived),
+        );
+
+        Ok(products)
+    }
+
+    /// Merge several products into one new product.
+    ///
+    /// The caller must own every source. The new product
+    /// is registered to `owner` and linked to all sources, which are then
+    /// retired. Emits `product_merged`.
+    pub fn merge_products(
+        env: Env,
+        owner: Address,
+        source_ids: Vec<String>,
+        config: ProductConfig,
+    ) -> Result<Product, Error> {
+        owner.require_auth();
+
+        ValidationContract::validate_batch_size(source_ids.len())?;
+        if source_ids.len() < 2 {
+            return Err(Error::MergeRequiresMultipleSources);
+        }
+
+        let mut seen: Map<String, bool> = Map::new(&env);
+        let mut sources = Vec::new(&env);
+        for i in 0..source_ids.len() {
+            let source_id = source_ids.get_unchecked(i);
+            if seen.contains_key(source_id.clone()) {
+                return Err(Error::DuplicateProductIdInBatch);
+            }
+            seen.set(source_id.clone(), true);
+            sources.push_back(read_owned_input(&env, &source_id, &owner)?);
+        }
+
+        let mut configs = Vec::new(&env);
+        configs.push_back(config);
+        validate_new_configs(&env, &configs)?;
+
+        let product = register_new_products(&env, &owner, &configs)?.get_unchecked(0);
+        storage::put_product_sources(&env, &product.id, &source_ids);
+
+        let mut derived = Vec::new(&env);
+        derived.push_back(product.id.clone());
+        let reason = String::from_str(&env, "merged");
+        for i in 0..sources.len() {
+            let mut source = sources.get_unchecked(i);
+            storage::put_derived_products(&env, &source.id, &derived);
+            retire_product(&env, &mut source, &owner, &reason);
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "product_merged"), product.id.clone()),
+            (owner, source_ids),
+        );
+
+        Ok(product)
+    }
+
+    /// Get the products a product was split or merged from.
+    pub fn get_sources(env: Env, id: String) -> Result<Vec<String>, Error> {
+        let _ = read_product(&env, &id)?;
+        Ok(storage::get_product_sources(&env, &id))
+    }
+
+    /// Get the products derived from a product by splitting or merging.
+    pub fn get_derivatives(env: Env, id: String) -> Result<Vec<String>, Error> {
+        let _ = read_product(&env, &id)?;
+        Ok(storage::get_derived_products(&env, &id))
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // PRODUCT STATUS
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Move a product to a new status.
+    ///
+    /// The owner or an authorized actor may change the status of an active
+    /// product. Fails with `InvalidStatusTransition` if the move isn't
+    /// allowed from the current status.
+    pub fn set_status(
+        env: Env,
+        caller: Address,
+        product_id: String,
+        status: ProductStatus,
+    ) -> Result<(), Error> {
+        let mut product = read_product(&env, &product_id)?;
+        caller.require_auth();
+        if !is_owner_or_authorized(&env, &product, &caller)? {
+            return Err(Error::Unauthorized);
+        }
+        if !product.active {
+            return Err(Error::ProductDeactivated);
+        }
+
+        change_status(&env, &mut product, status, &caller)
+    }
+
+    /// Apply the status mapped to a tracking event's type, if any.
+    ///
+    /// This must only be called by the configured tracking contract, before
+    /// it stores the event; an illegal transition rejects the event, as
+    /// does an actor who is neither the owner nor authorized.
+    pub fn record_event_status(
+        env: Env,
+        caller: Address,
+        actor: Address,
+        product_id: String,
+        event_type: Symbol,
+    ) -> Result<(), Error> {
+        require_tracking_contract(&env, &caller)?;
+        let mut product = read_product(&env, &product_id)?;
+        if !is_owner_or_authorized(&env, &product, &actor)? {
+            return Err(Error::Unauthorized);
+        }
+
+        match event_status(&env, &event_type) {
+            Some(status) => change_status(&env, &mut product, status, &actor),
+            None => Ok(()),
+        }
+    }
+
+    /// Check that an actor may add tracking events to a product: the
+    /// product must be active and the actor its owner or an authorized actor.
+    pub fn check_event_actor(env: Env, product_id: String, actor: Address) -> Result<(), Error> {
+        let product = read_product(&env, &product_id)?;
+        if !product.active {
+            return Err(Error::ProductDeactivated);
+        }
+        if !is_owner_or_authorized(&env, &product, &actor)? {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Allow or forbid a status transition. Admin only.
+    pub fn set_status_transition(
+        env: Env,
+        admin: Address,
+        from: ProductStatus,
+        to: ProductStatus,
+        allowed: bool,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        storage::set_status_transition(&env, from, to, allowed);
+
+        env.events().publish(
+            (Symbol::new(&env, "status_transition_set"),),
+            (from, to, allowed),
+        );
+
+        Ok(())
+    }
+
+    /// Whether a product may move from one status to another.
+    pub fn is_transition_allowed(env: Env, from: ProductStatus, to: ProductStatus) -> bool {
+        is_transition_allowed(&env, from, to)
+    }
+
+    /// Map a tracking event type to the status it moves products to, or
+    /// pass `None` so events of that type leave the status alone. Admin only.
+    pub fn set_event_status(
+        env: Env,
+        admin: Address,
+        event_type: Symbol,
+        status: Option<ProductStatus>,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        storage::set_event_status(&env, &event_type, &status);
+
+        env.events().publish(
+            (Symbol::new(&env, "event_status_set"), event_type),
+            status,
+        );
+
+        Ok(())
+    }
+
+    /// Get the status a tracking event type moves products to, if any.
+    pub fn get_event_status(env: Env, event_type: Symbol) -> Option<ProductStatus> {
+        event_status(&env, &event_type)
+    }
+
+    /// Get the number of products currently in a status.
+    pub fn get_status_count(env: Env, status: ProductStatus) -> u64 {
+        storage::get_status_count(&env, status)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // CERTIFICATIONS
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Attach a certificate to an active product.
+    ///
+    /// The issuer signs the call and is recorded on the certification, and
+    /// must currently be accredited for the standard in the configured
+    /// certifier registry. `expires_at` must be later than the current
+    /// ledger time. Returns the new certification's ID.
+    pub fn issue_certification(
+        env: Env,
+        issuer: Address,
+        product_id: String,
+        standard: Symbol,
+        document_hash: BytesN<32>,
+        expires_at: u64,
+    ) -> Result<u32, Error> {
+        issuer.require_auth();
+        let product = read_product(&env, &product_id)?;
+        if !product.active {
+            return Err(Error::ProductDeactivated);
+        }
+
+        let now = env.ledger().timestamp();
+        if expires_at <= now {
+            return Err(Error::InvalidCertificationExpiry);
+        }
+
+        let certifier_registry = get_certifier_registry(&env).ok_or(Error::NotInitialized)?;
+        let certifier_client = CertifierRegistryContractClient::new(&env, &certifier_registry);
+        if !certifier_client.is_accredited(&issuer, &standard, &now) {
+            return Err(Error::CertifierNotAccredited);
+        }
+
+        let certification = Certification {
+            id: storage::get_certification_count(&env, &product_id),
+            standard,
+            issuer,
+            document_hash,
+            issued_at: now,
+            expires_at,
+            revoked: false,
+        };
+        storage::put_certification(&env, &product_id, &certification);
+
+        env.events().publish(
+            (
+                Symbol::new(&env, "certification_issued"),
+                product_id,
+                certification.id,
+            ),
+            certification.clone(),
+        );
+
+        Ok(certification.id)
+    }
+
+    /// Revoke a certification. Only its issuer or the registry admin may
+    /// revoke it; the record is kept with `revoked` set.
+    pub fn revoke_certification(
+        env: Env,
+        caller: Address,
+        product_id: String,
+        certification_id: u32,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        let mut certification = storage::get_certification(&env, &product_id, certification_id)
+            .ok_or(Error::CertificationNotFound)?;
+
+        if certification.issuer != caller && storage::get_admin(&env) != Some(caller.clone()) {
+            return Err(Error::Unauthorized);
+        }
+        if certification.revoked {
+            return Err(Error::CertificationRevoked);
+        }
+
+        certification.revoked = true;
+        storage::put_certification(&env, &product_id, &certification);
+
+        env.events().publish(
+            (
+                Symbol::new(&env, "certification_revoked"),
+                product_id,
+                certification_id,
+            ),
+            caller,
+        );
+
+        Ok(())
+    }
+
+    /// Get every certification attached to a product, including expired
+    /// and revoked ones.
+    pub fn get_certifications(env: Env, id: String) -> Result<Vec<Certification>, Error> {
+        let _ = read_product(&env, &id)?;
+
+        let mut certifications = Vec::new(&env);
+        for i in 0..storage::get_certification_count(&env, &id) {
+            if let Some(certification) = storage::get_certification(&env, &id, i) {
+                certifications.push_back(certification);
+            }
+        }
+        Ok(certifications)
+    }
+
+    /// Get the certifications of a product that are neither revoked nor
+    /// expired at the current ledger time.
+    pub fn get_valid_certifications(env: Env, id: String) -> Result<Vec<Certification>, Error> {
+        let now = env.ledger().timestamp();
+        let mut valid = Vec::new(&env);
+        for certification in Self::get_certifications(env, id)?.iter() {
+            if !certification.revoked && certification.expires_at > now {
+                valid.push_back(certification);
+            }
+        }
+        Ok(valid)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // ALIASES
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Attach a partner's identifier to a product.
+    ///
+    /// The owner or an authorized actor may add aliases. A value can point
+    /// to only one product per namespace; the same value may be reused in
+    /// other namespaces.
+    pub fn add_alias(
+        env: Env,
+        caller: Address,
+        product_id: String,
+        namespace: Symbol,
+        value: String,
+    ) -> Result<(), Error> {
+        let product = read_product(&env, &product_id)?;
+        caller.require_auth();
+        if !is_owner_or_authorized(&env, &product, &caller)? {
+            return Err(Error::Unauthorized);
+        }
+
+        ValidationContract::validate_alias_value(&value)?;
+        let alias = ProductAlias { namespace, value };
+        if storage::get_alias_product(&env, &alias).is_some() {
+            return Err(Error::AliasAlreadyExists);
+        }
+        if storage::get_product_aliases(&env, &product_id).len() >= ValidationContract::MAX_ALIASES {
+            return Err(Error::InvalidInput);
+        }
+
+        storage::add_alias(&env, &product_id, &alias);
+
+        env.events().publish(
+            (Symbol::new(&env, "alias_added"), product_id),
+            (alias, caller),
+        );
+
+        Ok(())
+    }
+
+    /// Detach an alias from a product, freeing the value for reuse.
+    /// The owner or an authorized actor may remove aliases.
+    pub fn remove_alias(
+        env: Env,
+        caller: Address,
+        product_id: String,
+        namespace: Symbol,
+        value: String,
+    ) -> Result<(), Error> {
+        let product = read_product(&env, &product_id)?;
+        caller.require_auth();
+        if !is_owner_or_authorized(&env, &product, &caller)? {
+            return Err(Error::Unauthorized);
+        }
+
+        let alias = ProductAlias { namespace, value };
+        if storage::get_alias_product(&env, &alias) != Some(product_id.clone()) {
+            return Err(Error::InvalidInput);
+        }
+
+        storage::remove_alias(&env, &product_id, &alias);
+
+        env.events().publish(
+            (Symbol::new(&env, "alias_removed"), product_id),
+            (alias, caller),
+        );
+
+        Ok(())
+    }
+
+    /// Get the aliases attached to a product, oldest first.
+    pub fn get_aliases(env: Env, id: String) -> Result<Vec<ProductAlias>, Error> {
+        let _ = read_product(&env, &id)?;
+        Ok(storage::get_product_aliases(&env, &id))
+    }
+
+    /// Get the ID of the product an alias points to.
+    pub fn get_alias_target(env: Env, namespace: Symbol, value: String) -> Result<String, Error> {
+        storage::get_alias_product(&env, &ProductAlias { namespace, value })
+            .ok_or(Error::ProductNotFound)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // RECALLS
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Recall a product and everything made from it.
+    ///
+    /// The product owner or the registry admin may issue a recall, which
+    /// moves every affected product that hasn't been destroyed to the
+    /// `Recalled` status. It is
+    /// propagated to products split or merged from the recalled product and
+    /// to the assemblies it went into, transitively; products already under
+    /// recall keep their existing recall. Recalled products can't be
+    /// transferred, reactivated or used as inputs, and only `recall_*`
+    /// tracking events may be added to them.
+    ///
+    /// Returns the IDs of every product recalled by this call.
+    pub fn recall_product(
+        env: Env,
+        caller: Address,
+        product_id: String,
+        severity: RecallSeverity,
+        reason: String,
+        evidence_hash: BytesN<32>,
+    ) -> Result<Vec<String>, Error> {
+        let product = read_product(&env, &product_id)?;
+        caller.require_auth();
+        if product.owner != caller && storage::get_admin(&env) != Some(caller.clone()) {
+            return Err(Error::Unauthorized);
+        }
+        if is_recalled(&env, &product_id) {
+            return Err(Error::ProductAlreadyRecalled);
+        }
+        if reason.len() == 0 {
+            return Err(Error::RecallReasonRequired);
+        }
+
+        let mut queue = Vec::new(&env);
+        queue.push_back(product_id.clone());
+        let mut recalled = Vec::new(&env);
+
+        let mut i = 0;
+        while i < queue.len() {
+            let id = queue.get_unchecked(i);
+            i += 1;
+            if is_recalled(&env, &id) {
+                continue;
+            }
+
+            storage::put_product_recall(
+                &env,
+                &Recall {
+                    product_id: id.clone(),
+                    origin_id: product_id.clone(),
+                    severity,
+                    reason: reason.clone(),
+                    evidence_hash: evidence_hash.clone(),
+                    recalled_at: env.ledger().timestamp(),
+                    recalled_by: caller.clone(),
+                },
+            );
+
+            env.events().publish(
+                (Symbol::new(&env, "product_recalled"), id.clone()),
+                (product_id.clone(), severity, caller.clone()),
+            );
+
+            if let Some(mut recalled_product) = storage::get_product(&env, &id) {
+                if recalled_product.status != ProductStatus::Destroyed {
+                    move_status(&env, &mut recalled_product, ProductStatus::Recalled, &caller);
+                }
+            }
+
+            queue.append(&storage::get_derived_products(&env, &id));
+            if let Some(parent_id) = storage::get_product_parent(&env, &id) {
+                queue.push_back(parent_id);
+            }
+            recalled.push_back(id);
+        }
+
+        Ok(recalled)
+    }
+
+    /// Whether a product is under recall, for point-of-sale checks.
+    /// Unknown products are reported as not recalled.
+    pub fn is_recalled(env: Env, id: String) -> bool {
+        is_recalled(&env, &id)
+    }
+
+    /// Get the recall placed on a product, if any.
+    pub fn get_recall(env: Env, id: String) -> Option<Recall> {
+        storage::get_product_recall(&env, &id)
+    }
+
+    /// Get every recall in force, in the order the products were recalled.
+    pub fn get_active_recalls(env: Env) -> Vec<Recall> {
+        let mut recalls = Vec::new(&env);
+        let total = storage::get_recall_count(&env);
+
+        // The recall list is paged at the storage layer's 1000-entry cap.
+        let mut offset = 0;
+        while offset < total {
+            let ids = storage::get_recalled_product_ids(&env, offset, 1000);
+            for i in 0..ids.len() {
+                if let Some(recall) = storage::get_product_recall(&env, &ids.get_unchecked(i)) {
+                    recalls.push_back(recall);
+                }
+            }
+            offset += 1000;
+        }
+
+        recalls
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // PRODUCT LIFECYCLE — DEACTIVATION & REACTIVATION
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Deactivate a product.
+    ///
+    /// Only the product owner can deactivate. A reason must be provided.
+    /// Deactivation prevents new tracking events and decrements the active
+    /// product counter.
+    pub fn deactivate_product(
+        env: Env,
+        owner: Address,
+        product_id: String,
+        reason: String,
+    ) -> Result<(), Error> {
+        let mut product = read_product(&env, &product_id)?;
+        require_owner(&product, &owner)?;
+
+        if !product.active {
+            return Err(Error::ProductDeactivated);
+        }
+
+        ValidationContract::validate_deactivation_reason(&reason)?;
+
+        retire_product(&env, &mut product, &owner, &reason);
+
+        env.events().publish(
+            (Symbol::new(&env, "product_deactivated"), product_id.clone()),
+            (owner, reason),
+        );
+
+        Ok(())
+    }
+
+    /// Reactivate a previously deactivated product.
+    ///
+    /// Only the product owner can reactivate. Recalled products and products
+    /// used up by assembly, splitting or merging stay retired. Clears
+    /// deactivation info and increments the active product counter; the
+    /// reason, which may be empty, is kept in the lifecycle log.
+    pub fn reactivate_product(
+        env: Env,
+        owner: Address,
+        product_id: String,
+        reason: String,
+    ) -> Result<(), Error> {
+        let mut product = read_product(&env, &product_id)?;
+        require_owner(&product, &owner)?;
+
+        if product.active {
+            return Err(Error::ProductAlreadyActive);
+        }
+        if is_recalled(&env, &product_id) {
+            return Err(Error::ProductRecalled);
+        }
+        if is_used_up(&env, &product_id) {
+            return Err(Error::ProductConsumed);
+        }
+
+        product.active = true;
+        product.deactivation_info = Vec::new(&env);
+
+        write_product(&env, &product);
+        index_attributes(&env, &product, true);
+        log_lifecycle(&env, &product_id, LifecycleAction::Reactivated, &owner, &reason);
+
+        // Increment active counter
+        let active = storage::get_active_products(&env) + 1;
+        storage::set_active_products(&env, active);
+
+        env.events().publish(
+            (Symbol::new(&env, "product_reactivated"), product_id.clone()),
+            (owner, reason),
+        );
+
+        Ok(())
+    }
+
+    /// Get a page of a product's lifecycle log, oldest entry first.
+    ///
+    /// Every deactivation and reactivation is kept, including retirements
+    /// caused by assembly, splitting or merging.
+    pub fn get_lifecycle_history(
+        env: Env,
+        id: String,
+        offset: u64,
+        limit: u64,
+    ) -> Result<LifecyclePage, Error> {
+        let _ = read_product(&env, &id)?;
+
+        let total_count = storage::get_lifecycle_count(&env, &id);
+        let entries = storage::get_lifecycle_entries(&env, &id, offset, limit);
+        let has_more = offset + (entries.len() as u64) < total_count;
+
+        Ok(LifecyclePage {
+            entries,
+            total_count,
+            has_more,
+        })
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // STORAGE TTL
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Extend the storage TTL of a product and everything kept for it,
+    /// including its tracking events when a tracking contract is
+    /// configured. Anyone may call this.
+    ///
+    /// The product's lists, such as its revisions, lifecycle log and
+    /// events, are extended a page at a time: call again with the next
+    /// `offset` while this returns `true`. `limit` must be between 1 and
+    /// 1000.
+    pub fn bump_product(env: Env, id: String, offset: u64, limit: u64) -> Result<bool, Error> {
+        if limit == 0 || limit > 1000 {
+            return Err(Error::InvalidInput);
+        }
+        let product = read_product(&env, &id)?;
+        let mut has_more = storage::extend_product(&env, &product, offset, limit);
+
+        if let Some(tracking_contract) = get_tracking_contract(&env) {
+            has_more |= TrackingContractClient::new(&env, &tracking_contract)
+                .bump_product_events(&id, &offset, &limit);
+        }
+
+        Ok(has_more)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // PRODUCT QUERIES
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Get a product by its string ID.
+    pub fn get_product(env: Env, id: String) -> Result<Product, Error> {
+        read_product(&env, &id)
+    }
+
+    /// Look up a product registered under the SGTIN scheme by its GTIN
+    /// (8, 12, 13 or 14 digits) and serial number.
+    pub fn get_product_by_gtin_serial(
+        env: Env,
+        gtin: String,
+        serial: String,
+    ) -> Result<Product, Error> {
+        if gtin.len() as usize > gs1::GTIN14_LEN {
+            return Err(Error::InvalidIdentifier);
+        }
+        if serial.len() as usize > gs1::MAX_SERIAL_LEN {
+            return Err(Error::InvalidIdentifier);
+        }
+
+        let mut gtin_buf = [0u8; gs1::GTIN14_LEN];
+        let gtin_bytes = &mut gtin_buf[..gtin.len() as usize];
+        gtin.copy_into_slice(gtin_bytes);
+        let gtin14 = gs1::to_gtin14(gtin_bytes)?;
+
+        let mut serial_buf = [0u8; gs1::MAX_SERIAL_LEN];
+        let serial_bytes = &mut serial_buf[..serial.len() as usize];
+        serial.copy_into_slice(serial_bytes);
+        gs1::validate_serial(serial_bytes)?;
+
+        // Rebuild the element string the product was registered under
+        let mut id_buf = [0u8; ValidationContract::MAX_PRODUCT_ID_LEN as usize];
+        let mut len = 0;
+        for part in [gs1::SGTIN_GTIN_AI, &gtin14, gs1::SGTIN_SERIAL_AI, serial_bytes] {
+            id_buf[len..len + part.len()].copy_from_slice(part);
+            len += part.len();
+        }
+        let id = String::from_bytes(&env, &id_buf[..len]);
+
+        match storage::get_product(&env, &id) {
+            Some(product) if product.id_scheme == IdScheme::Sgtin => Ok(product),
+            _ => Err(Error::ProductNotFound),
+        }
+    }
+
+    /// Read a page of the global product catalogue in registration order.
+    ///
+    /// Scans up to `limit` index entries starting at `cursor`. When inactive
+    /// products are excluded a page may hold fewer than `limit` products, so
+    /// callers should keep following `next_cursor` while `has_more` is set.
+    pub fn get_catalogue_page(
+        env: Env,
+        cursor: u64,
+        limit: u64,
+        include_inactive: bool,
+    ) -> ProductCursorPage {
+        let mut products = Vec::new(&env);
+
+        if limit == 0 || limit > 1000 {
+            return ProductCursorPage {
+                products,
+                next_cursor: cursor,
+                has_more: false,
+            };
+        }
+
+        let total = storage::get_product_index_count(&env);
+        let end = cursor.saturating_add(limit).min(total);
+
+        for i in cursor..end {
+            if let Some(id) = storage::get_product_id_at(&env, i) {
+                if let Some(product) = storage::get_product(&env, &id) {
+                    if include_inactive || product.active {
+                        products.push_back(product);
+                    }
+                }
+            }
+        }
+
+        let next_cursor = end.max(cursor);
+
+        ProductCursorPage {
+            products,
+            next_cursor,
+            has_more: next_cursor < total,
+        }
+    }
+
+    /// Get a page of the products currently owned by an address.
+    pub fn get_products_by_owner(
+        env: Env,
+        owner: Address,
+        offset: u64,
+        limit: u64,
+    ) -> ProductPage {
+        let total_count = storage::get_owner_product_count(&env, &owner);
+        let ids = storage::get_owner_product_ids(&env, &owner, offset, limit);
+        load_product_page(&env, ids, offset, total_count)
+    }
+
+    /// Get the number of products currently owned by an address.
+    pub fn get_owner_product_count(env: Env, owner: Address) -> u64 {
+        storage::get_owner_product_count(&env, &owner)
+    }
+
+    /// Get a page of the products in a category (exact match).
+    pub fn get_category_page(
+        env: Env,
+        category: String,
+        offset: u64,
+        limit: u64,
+        active_only: bool,
+    ) -> ProductPage {
+        let total_count = storage::get_category_product_count(&env, &category, active_only);
+        let ids = storage::get_category_product_ids(&env, &category, active_only, offset, limit);
+        load_product_page(&env, ids, offset, total_count)
+    }
+
+    /// Get a page of the products carrying a tag (exact match).
+    pub fn get_tag_page(
+        env: Env,
+        tag: String,
+        offset: u64,
+        limit: u64,
+        active_only: bool,
+    ) -> ProductPage {
+        let total_count = storage::get_tag_product_count(&env, &tag, active_only);
+        let ids = storage::get_tag_product_ids(&env, &tag, active_only, offset, limit);
+        load_product_page(&env, ids, offset, total_count)
+    }
+
+    /// Get total and active product counts for a category.
+    pub fn get_category_stats(env: Env, category: String) -> ProductStats {
+        ProductStats {
+            total_products: storage::get_category_product_count(&env, &category, false),
+            active_products: storage::get_category_product_count(&env, &category, true),
+        }
     }
 
     /// Get global product statistics.
@@ -1,25 +1,26 @@
 use soroban_sdk::{contract, contractimpl, Address, Env, String, Symbol};
 
 use crate::error::Error;
+use crate::storage_contract::StorageContract;
 use crate::{AuthorizationContractClient, ProductRegistryContractClient};
 use crate::types::DataKey;
 
 // ─── Storage helpers for ProductTransferContract ─────────────────────────────
 
 fn get_auth_contract(env: &Env) -> Option<Address> {
-    env.storage().persistent().get(&DataKey::AuthContract)
+    StorageContract::read_instance(env, &DataKey::AuthContract)
 }
 
 fn set_auth_contract(env: &Env, address: &Address) {
-    env.storage().persistent().set(&DataKey::AuthContract, address);
+    StorageContract::write_instance(env, &DataKey::AuthContract, address);
 }
 
 fn get_main_contract(env: &Env) -> Option<Address> {
-    env.storage().persistent().get(&DataKey::MainContract)
+    StorageContract::read_instance(env, &DataKey::MainContract)
 }
 
 fn set_main_contract(env: &Env, address: &Address) {
-    env.storage().persistent().set(&DataKey::MainContract, address);
+    StorageContract::write_instance(env, &DataKey::MainContract, address);
 }
 
 // ─── Contract ────────────────────────────────────────────────────────────────
@@ -80,6 +81,10 @@ impl ProductTransferContract {
             return Err(Error::ProductDeactivated);
         }
 
+        if pr_client.is_recalled(&product_id) {
+            return Err(Error::ProductRecalled);
+        }
+
         // Update authorization mappings via AuthorizationContract
         let auth_client = AuthorizationContractClient::new(&env, &auth_contract);
         auth_client.update_product_owner(&owner, &product_id, &new_owner);
@@ -171,6 +176,10 @@ impl ProductTransferContract {
                     continue;
                 }
 
+                if pr_client.is_recalled(&product_id) {
+                    continue;
+                }
+
                 // Update authorization mappings
                 auth_client.update_product_owner(&owner, &product_id, &new_owner);
 
@@ -194,11 +203,11 @@ impl ProductTransferContract {
 #[cfg(test)]
 mod test_product_transfer {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Address, Env, Map, String, Vec};
+    use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, Map, String, Vec};
     use crate::{
         AuthorizationContract, AuthorizationContractClient,
         ProductRegistryContract, ProductRegistryContractClient,
-        ProductConfig,
+        IdScheme, ProductConfig, RecallSeverity,
     };
 
     fn setup(env: &Env) -> (ProductRegistryContractClient, AuthorizationContractClient, Address, ProductTransferContractClient, Address) {
@@ -231,6 +240,7 @@ mod test_product_transfer {
             owner,
             &ProductConfig {
                 id: id.clone(),
+                id_scheme: IdScheme::Custom,
                 name: String::from_str(env, "Test Product"),
                 description: String::from_str(env, "Description"),
                 origin_location: String::from_str(env, "Origin"),
@@ -367,6 +377,61 @@ mod test_product_transfer {
         assert_eq!(count, 2);
     }
 
+    #[test]
+    fn test_transfer_updates_owner_index() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (pr_client, _auth_client, _admin, transfer_client, _pr_id) = setup(&env);
+
+        let owner = Address::generate(&env);
+        let new_owner = Address::generate(&env);
+
+        let id1 = register_test_product(&env, &pr_client, &owner, "PROD1");
+        let id2 = register_test_product(&env, &pr_client, &owner, "PROD2");
+        let id3 = register_test_product(&env, &pr_client, &owner, "PROD3");
+
+        transfer_client.transfer_product(&owner, &id1, &new_owner);
+
+        assert_eq!(pr_client.get_owner_product_count(&owner), 2);
+        assert_eq!(pr_client.get_owner_product_count(&new_owner), 1);
+
+        let page = pr_client.get_products_by_owner(&owner, &0, &10);
+        assert_eq!(page.products.len(), 2);
+        let ids: Vec<String> = Vec::from_array(
+            &env,
+            [page.products.get_unchecked(0).id, page.products.get_unchecked(1).id],
+        );
+        assert!(ids.contains(&id2));
+        assert!(ids.contains(&id3));
+
+        let page = pr_client.get_products_by_owner(&new_owner, &0, &10);
+        assert_eq!(page.products.get_unchecked(0).id, id1);
+    }
+
+    #[test]
+    fn test_batch_transfer_updates_owner_index() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (pr_client, _auth_client, _admin, transfer_client, _pr_id) = setup(&env);
+
+        let owner = Address::generate(&env);
+        let new_owner = Address::generate(&env);
+
+        let id1 = register_test_product(&env, &pr_client, &owner, "PROD1");
+        let id2 = register_test_product(&env, &pr_client, &owner, "PROD2");
+
+        let mut product_ids = Vec::new(&env);
+        product_ids.push_back(id1);
+        product_ids.push_back(id2);
+        transfer_client.batch_transfer_products(&owner, &product_ids, &new_owner);
+
+        assert_eq!(pr_client.get_owner_product_count(&owner), 0);
+        assert_eq!(pr_client.get_owner_product_count(&new_owner), 2);
+        assert!(pr_client.get_products_by_owner(&owner, &0, &10).products.is_empty());
+    }
+
     #[test]
     fn test_batch_transfer_empty_fails() {
         let env = Env::default();
@@ -491,6 +556,39 @@ mod test_product_transfer {
         assert_eq!(count, 1); // Should only transfer owned products
     }
 
+    #[test]
+    fn test_recalled_product_cannot_be_transferred() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (pr_client, _auth_client, _admin, transfer_client, _pr_id) = setup(&env);
+
+        let owner = Address::generate(&env);
+        let new_owner = Address::generate(&env);
+        let id1 = register_test_product(&env, &pr_client, &owner, "PROD1");
+        let id2 = register_test_product(&env, &pr_client, &owner, "PROD2");
+
+        pr_client.recall_product(
+            &owner,
+            &id1,
+            &RecallSeverity::High,
+            &String::from_str(&env, "Contamination"),
+            &BytesN::from_array(&env, &[0; 32]),
+        );
+
+        let res = transfer_client.try_transfer_product(&owner, &id1, &new_owner);
+        assert_eq!(res, Err(Ok(Error::ProductRecalled)));
+
+        // Batch transfers skip recalled products
+        let mut batch = Vec::new(&env);
+        batch.push_back(id1.clone());
+        batch.push_back(id2.clone());
+        let count = transfer_client.batch_transfer_products(&owner, &batch, &new_owner);
+        assert_eq!(count, 1);
+        assert_eq!(pr_client.get_product(&id1).owner, owner);
+        assert_eq!(pr_client.get_product(&id2).owner, new_owner);
+    }
+
     #[test]
     fn test_init_already_initialized_fails() {
         let env = Env::default();
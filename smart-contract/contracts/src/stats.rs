@@ -1,8 +1,9 @@
 use soroban_sdk::{contract, contractimpl, Address, Env, String, Symbol, Vec, contracttype};
  
 use crate::error::Error;
-use crate::types::{DataKey, ProductStats};
-use crate::{ProductRegistryContractClient, TrackingContractClient};
+use crate::storage_contract::StorageContract;
+use crate::types::{DataKey, ProductStats, ProductStatus};
+use crate::{EventTypeRegistryContractClient, ProductRegistryContractClient, TrackingContractClient};
 
 #[contracttype]
 #[derive(Clone)]
@@ -14,19 +15,41 @@ enum StatsDataKey {
 // ─── Storage helpers for StatsContract ───────────────────────────────────────
 
 fn get_registry_contract(env: &Env) -> Option<Address> {
-    env.storage().persistent().get(&StatsDataKey::RegistryContract)
+    StorageContract::read_instance(env, &StatsDataKey::RegistryContract)
 }
 
 fn set_registry_contract(env: &Env, address: &Address) {
-    env.storage().persistent().set(&StatsDataKey::RegistryContract, address);
+    StorageContract::write_instance(env, &StatsDataKey::RegistryContract, address);
 }
 
 fn get_tracking_contract(env: &Env) -> Option<Address> {
-    env.storage().persistent().get(&StatsDataKey::TrackingContract)
+    StorageContract::read_instance(env, &StatsDataKey::TrackingContract)
 }
 
 fn set_tracking_contract(env: &Env, address: &Address) {
-    env.storage().persistent().set(&StatsDataKey::TrackingContract, address);
+    StorageContract::write_instance(env, &StatsDataKey::TrackingContract, address);
+}
+
+// ─── Internal helpers ────────────────────────────────────────────────────────
+
+/// Event types to count: every type in the tracking contract's event
+/// type registry, or a set of common types if it has none.
+fn known_event_types(env: &Env, tracking_client: &TrackingContractClient) -> Vec<Symbol> {
+    let mut event_types = Vec::new(env);
+    match tracking_client.get_event_type_registry() {
+        Some(registry) => {
+            let registry_client = EventTypeRegistryContractClient::new(env, &registry);
+            for info in registry_client.list_event_types().iter() {
+                event_types.push_back(info.event_type);
+            }
+        }
+        None => {
+            for name in ["created", "shipped", "received", "transferred", "updated"] {
+                event_types.push_back(Symbol::new(env, name));
+            }
+        }
+    }
+    event_types
 }
 
 // ─── Contract ────────────────────────────────────────────────────────────────
@@ -83,6 +106,13 @@ impl StatsContract {
         Ok(total.saturating_sub(active))
     }
 
+    /// Get the number of products currently in the given status.
+    pub fn get_products_by_status(env: Env, status: ProductStatus) -> Result<u64, Error> {
+        let registry_contract = get_registry_contract(&env).ok_or(Error::NotInitialized)?;
+        let registry_client = ProductRegistryContractClient::new(&env, &registry_contract);
+        Ok(registry_client.get_status_count(&status))
+    }
+
     /// Get the total number of tracking events across all products.
     pub fn get_total_events(env: Env) -> Result<u64, Error> {
         let _ = get_tracking_contract(&env).ok_or(Error::NotInitialized)?;
@@ -145,16 +175,7 @@ impl StatsContract {
         // Count events by type
         let mut type_counts = Vec::new(&env);
 
-        // Common event types to check
-        let event_types = [
-            Symbol::new(&env, "created"),
-            Symbol::new(&env, "shipped"),
-            Symbol::new(&env, "received"),
-            Symbol::new(&env, "transferred"),
-            Symbol::new(&env, "updated"),
-        ];
-
-        for event_type in event_types.iter() {
+        for event_type in known_event_types(&env, &tracking_client).iter() {
             let count = tracking_client.get_event_count_by_type(&product_id, &event_type);
             if count > 0 {
                 type_counts.push_back((event_type.clone(), count));
@@ -170,13 +191,22 @@ mod test_stats {
     use super::*;
     use soroban_sdk::{testutils::Address as _, Address, Env, Map};
     use crate::{
-        AuthorizationContract, AuthorizationContractClient,
+        storage, AuthorizationContract, AuthorizationContractClient, ChainLogisticsContract,
+        ChainLogisticsContractClient, EventTypeRegistryContract,
         ProductRegistryContract, ProductRegistryContractClient,
-        ProductConfig, TrackingContract, TrackingContractClient,
+        IdScheme, ProductConfig, TrackingContract, TrackingContractClient,
     };
 
     fn setup(env: &Env) -> (ProductRegistryContractClient, TrackingContractClient, super::StatsContractClient) {
+        let (registry_client, tracking_client, stats_client, _admin) = setup_with_admin(env);
+        (registry_client, tracking_client, stats_client)
+    }
+
+    fn setup_with_admin(
+        env: &Env,
+    ) -> (ProductRegistryContractClient<'_>, TrackingContractClient<'_>, super::StatsContractClient<'_>, Address) {
         let auth_id = env.register_contract(None, AuthorizationContract);
+        let cl_id = env.register_contract(None, ChainLogisticsContract);
         let registry_id = env.register_contract(None, ProductRegistryContract);
         let tracking_id = env.register_contract(None, TrackingContract);
         let stats_id = env.register_contract(None, super::StatsContract);
@@ -189,10 +219,15 @@ mod test_stats {
         auth_client.configure_initializer(&registry_id);
         registry_client.configure_auth_contract(&auth_id);
 
-        tracking_client.init(&registry_id);
+        let admin = Address::generate(env);
+        ChainLogisticsContractClient::new(env, &cl_id).init(&admin, &auth_id);
+        tracking_client.init(&cl_id);
+        registry_client.configure_admin(&admin);
+        registry_client.configure_tracking_contract(&admin, &tracking_id);
+        tracking_client.configure_registry(&admin, &registry_id);
         stats_client.init(&registry_id, &tracking_id);
 
-        (registry_client, tracking_client, stats_client)
+        (registry_client, tracking_client, stats_client, admin)
     }
 
     fn register_test_product(
@@ -206,6 +241,7 @@ mod test_stats {
             owner,
             &ProductConfig {
                 id: product_id.clone(),
+                id_scheme: IdScheme::Custom,
                 name: String::from_str(env, "Test Product"),
                 description: String::from_str(env, "Description"),
                 origin_location: String::from_str(env, "Origin"),
@@ -385,6 +421,39 @@ mod test_stats {
         assert_eq!(distribution.len(), 0);
     }
 
+    #[test]
+    fn test_event_type_distribution_uses_registry() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (registry_client, tracking_client, stats_client, admin) = setup_with_admin(&env);
+        let owner = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &owner, "PROD1");
+
+        let event_types_id = env.register_contract(None, EventTypeRegistryContract);
+        let event_types_client = EventTypeRegistryContractClient::new(&env, &event_types_id);
+        event_types_client.event_types_init(&admin);
+        tracking_client.configure_event_types(&admin, &event_types_id);
+
+        let shipped = Symbol::new(&env, "shipped");
+        let inspected = Symbol::new(&env, "inspected");
+        for event_type in [shipped.clone(), inspected.clone()] {
+            let description = String::from_str(&env, "");
+            event_types_client.register_event_type(&admin, &event_type, &description, &Vec::new(&env));
+        }
+
+        env.as_contract(&tracking_client.address, || {
+            storage::index_event_by_type(&env, &product_id, &shipped, 1);
+            storage::index_event_by_type(&env, &product_id, &inspected, 2);
+            storage::index_event_by_type(&env, &product_id, &shipped, 3);
+        });
+
+        let distribution = stats_client.get_event_type_distribution(&product_id);
+        assert_eq!(distribution.len(), 2);
+        assert_eq!(distribution.get_unchecked(0), (shipped, 2));
+        assert_eq!(distribution.get_unchecked(1), (inspected, 1));
+    }
+
     #[test]
     fn test_get_event_type_distribution_not_found() {
         let env = Env::default();
@@ -397,6 +466,23 @@ mod test_stats {
         assert_eq!(res, Err(Ok(Error::ProductNotFound)));
     }
 
+    #[test]
+    fn test_get_products_by_status() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (registry_client, _tracking_client, stats_client) = setup(&env);
+        let owner = Address::generate(&env);
+        register_test_product(&env, &registry_client, &owner, "PROD1");
+        let product_id = register_test_product(&env, &registry_client, &owner, "PROD2");
+
+        registry_client.set_status(&owner, &product_id, &ProductStatus::InStorage);
+
+        assert_eq!(stats_client.get_products_by_status(&ProductStatus::Registered), 1);
+        assert_eq!(stats_client.get_products_by_status(&ProductStatus::InStorage), 1);
+        assert_eq!(stats_client.get_products_by_status(&ProductStatus::Sold), 0);
+    }
+
     #[test]
     fn test_init_already_initialized_fails() {
         let env = Env::default();
@@ -1,7 +1,9 @@
-use soroban_sdk::{Address, Env, String, Symbol, Vec};
+use soroban_sdk::{Address, BytesN, Env, String, Symbol, Vec};
 
 use crate::storage_contract::StorageContract;
-use crate::types::{Product, TrackingEvent};
+use crate::types::{
+    Certification, EventAmendment, EventSequence, Excursion, LifecycleEntry, Product, ProductAlias, ProductRevision, ProductStatus, Recall, SequenceMark, ThresholdPolicy, TimestampWindows, TrackingEvent,
+};
 
 pub fn get_auth_contract(env: &Env) -> Option<Address> {
     StorageContract::get_auth_contract(env)
@@ -25,6 +27,203 @@ pub fn get_product(env: &Env, product_id: &String) -> Option<Product> {
     StorageContract::get_product(env, product_id)
 }
 
+// ─── Product revisions ──────────────────────────────────────────────────────
+
+pub fn put_product_revision(env: &Env, revision: &ProductRevision) {
+    StorageContract::put_product_revision(env, revision)
+}
+
+pub fn get_product_revision(env: &Env, product_id: &String, revision: u32) -> Option<ProductRevision> {
+    StorageContract::get_product_revision(env, product_id, revision)
+}
+
+pub fn get_product_revision_count(env: &Env, product_id: &String) -> u32 {
+    StorageContract::get_product_revision_count(env, product_id)
+}
+
+// ─── Certifications ─────────────────────────────────────────────────────────
+
+pub fn put_certification(env: &Env, product_id: &String, certification: &Certification) {
+    StorageContract::put_certification(env, product_id, certification)
+}
+
+pub fn get_certification(env: &Env, product_id: &String, id: u32) -> Option<Certification> {
+    StorageContract::get_certification(env, product_id, id)
+}
+
+pub fn get_certification_count(env: &Env, product_id: &String) -> u32 {
+    StorageContract::get_certification_count(env, product_id)
+}
+
+// ─── Aliases ────────────────────────────────────────────────────────────────
+
+pub fn get_alias_product(env: &Env, alias: &ProductAlias) -> Option<String> {
+    StorageContract::get_alias_product(env, alias)
+}
+
+pub fn get_product_aliases(env: &Env, product_id: &String) -> Vec<ProductAlias> {
+    StorageContract::get_product_aliases(env, product_id)
+}
+
+pub fn add_alias(env: &Env, product_id: &String, alias: &ProductAlias) {
+    StorageContract::add_alias(env, product_id, alias)
+}
+
+pub fn remove_alias(env: &Env, product_id: &String, alias: &ProductAlias) {
+    StorageContract::remove_alias(env, product_id, alias)
+}
+
+// ─── Lifecycle log ──────────────────────────────────────────────────────────
+
+pub fn append_lifecycle_entry(env: &Env, product_id: &String, entry: &LifecycleEntry) {
+    StorageContract::append_lifecycle_entry(env, product_id, entry)
+}
+
+pub fn get_lifecycle_entries(
+    env: &Env,
+    product_id: &String,
+    offset: u64,
+    limit: u64,
+) -> Vec<LifecycleEntry> {
+    StorageContract::get_lifecycle_entries(env, product_id, offset, limit)
+}
+
+pub fn get_lifecycle_count(env: &Env, product_id: &String) -> u64 {
+    StorageContract::get_lifecycle_count(env, product_id)
+}
+
+// ─── Composition ────────────────────────────────────────────────────────────
+
+pub fn put_product_components(env: &Env, product_id: &String, components: &Vec<String>) {
+    StorageContract::put_product_components(env, product_id, components)
+}
+
+pub fn get_product_components(env: &Env, product_id: &String) -> Vec<String> {
+    StorageContract::get_product_components(env, product_id)
+}
+
+pub fn set_product_parent(env: &Env, product_id: &String, parent_id: &String) {
+    StorageContract::set_product_parent(env, product_id, parent_id)
+}
+
+pub fn get_product_parent(env: &Env, product_id: &String) -> Option<String> {
+    StorageContract::get_product_parent(env, product_id)
+}
+
+// ─── Lineage ────────────────────────────────────────────────────────────────
+
+pub fn put_product_sources(env: &Env, product_id: &String, sources: &Vec<String>) {
+    StorageContract::put_product_sources(env, product_id, sources)
+}
+
+pub fn get_product_sources(env: &Env, product_id: &String) -> Vec<String> {
+    StorageContract::get_product_sources(env, product_id)
+}
+
+pub fn put_derived_products(env: &Env, product_id: &String, derived: &Vec<String>) {
+    StorageContract::put_derived_products(env, product_id, derived)
+}
+
+pub fn get_derived_products(env: &Env, product_id: &String) -> Vec<String> {
+    StorageContract::get_derived_products(env, product_id)
+}
+
+// ─── Recalls ─────────────────────────────────────────────────────────────────
+
+pub fn put_product_recall(env: &Env, recall: &Recall) {
+    StorageContract::put_product_recall(env, recall)
+}
+
+pub fn get_product_recall(env: &Env, product_id: &String) -> Option<Recall> {
+    StorageContract::get_product_recall(env, product_id)
+}
+
+pub fn get_recalled_product_ids(env: &Env, offset: u64, limit: u64) -> Vec<String> {
+    StorageContract::get_recalled_product_ids(env, offset, limit)
+}
+
+pub fn get_recall_count(env: &Env) -> u64 {
+    StorageContract::get_recall_count(env)
+}
+
+// ─── Global product index ───────────────────────────────────────────────────
+
+pub fn append_product_index(env: &Env, product_id: &String) {
+    StorageContract::append_product_index(env, product_id)
+}
+
+pub fn get_product_id_at(env: &Env, index: u64) -> Option<String> {
+    StorageContract::get_product_id_at(env, index)
+}
+
+pub fn get_product_index_count(env: &Env) -> u64 {
+    StorageContract::get_product_index_count(env)
+}
+
+// ─── Owner index ────────────────────────────────────────────────────────────
+
+pub fn add_owner_product(env: &Env, owner: &Address, product_id: &String) {
+    StorageContract::add_owner_product(env, owner, product_id)
+}
+
+pub fn remove_owner_product(env: &Env, owner: &Address, product_id: &String) {
+    StorageContract::remove_owner_product(env, owner, product_id)
+}
+
+pub fn get_owner_product_ids(env: &Env, owner: &Address, offset: u64, limit: u64) -> Vec<String> {
+    StorageContract::get_owner_product_ids(env, owner, offset, limit)
+}
+
+pub fn get_owner_product_count(env: &Env, owner: &Address) -> u64 {
+    StorageContract::get_owner_product_count(env, owner)
+}
+
+// ─── Category & tag indexes ─────────────────────────────────────────────────
+
+pub fn add_category_product(env: &Env, category: &String, active_only: bool, product_id: &String) {
+    StorageContract::add_category_product(env, category, active_only, product_id)
+}
+
+pub fn remove_category_product(env: &Env, category: &String, active_only: bool, product_id: &String) {
+    StorageContract::remove_category_product(env, category, active_only, product_id)
+}
+
+pub fn get_category_product_ids(
+    env: &Env,
+    category: &String,
+    active_only: bool,
+    offset: u64,
+    limit: u64,
+) -> Vec<String> {
+    StorageContract::get_category_product_ids(env, category, active_only, offset, limit)
+}
+
+pub fn get_category_product_count(env: &Env, category: &String, active_only: bool) -> u64 {
+    StorageContract::get_category_product_count(env, category, active_only)
+}
+
+pub fn add_tag_product(env: &Env, tag: &String, active_only: bool, product_id: &String) {
+    StorageContract::add_tag_product(env, tag, active_only, product_id)
+}
+
+pub fn remove_tag_product(env: &Env, tag: &String, active_only: bool, product_id: &String) {
+    StorageContract::remove_tag_product(env, tag, active_only, product_id)
+}
+
+pub fn get_tag_product_ids(
+    env: &Env,
+    tag: &String,
+    active_only: bool,
+    offset: u64,
+    limit: u64,
+) -> Vec<String> {
+    StorageContract::get_tag_product_ids(env, tag, active_only, offset, limit)
+}
+
+pub fn get_tag_product_count(env: &Env, tag: &String, active_only: bool) -> u64 {
+    StorageContract::get_tag_product_count(env, tag, active_only)
+}
+
 // ─── Event IDs per product ───────────────────────────────────────────────────
 
 pub fn put_product_event_ids(env: &Env, product_id: &String, ids: &Vec<u64>) {
@@ -46,6 +245,102 @@ pub fn get_product_event_ids_paginated(
 
 // ─── Events ─────────────────────────────────────────────────────────────────
 
+pub fn get_product_event_head(env: &Env, product_id: &String) -> Option<BytesN<32>> {
+    StorageContract::get_product_event_head(env, product_id)
+}
+
+pub fn set_product_event_head(env: &Env, product_id: &String, hash: &BytesN<32>) {
+    StorageContract::set_product_event_head(env, product_id, hash)
+}
+
+pub fn get_aggregate_products(env: &Env, event_id: u64) -> Vec<String> {
+    StorageContract::get_aggregate_products(env, event_id)
+}
+
+pub fn set_aggregate_products(env: &Env, event_id: u64, product_ids: &Vec<String>) {
+    StorageContract::set_aggregate_products(env, event_id, product_ids)
+}
+
+pub fn get_aggregate_link(env: &Env, event_id: u64, product_id: &String) -> Option<BytesN<32>> {
+    StorageContract::get_aggregate_link(env, event_id, product_id)
+}
+
+pub fn set_aggregate_link(env: &Env, event_id: u64, product_id: &String, hash: &BytesN<32>) {
+    StorageContract::set_aggregate_link(env, event_id, product_id, hash)
+}
+
+pub fn get_threshold_policy(env: &Env, product_id: &String) -> Option<ThresholdPolicy> {
+    StorageContract::get_threshold_policy(env, product_id)
+}
+
+pub fn set_threshold_policy(env: &Env, product_id: &String, policy: &ThresholdPolicy) {
+    StorageContract::set_threshold_policy(env, product_id, policy)
+}
+
+pub fn get_excursions(env: &Env, product_id: &String) -> Vec<Excursion> {
+    StorageContract::get_excursions(env, product_id)
+}
+
+pub fn get_excursion(env: &Env, product_id: &String, index: u32) -> Option<Excursion> {
+    StorageContract::get_excursion(env, product_id, index)
+}
+
+pub fn get_excursion_count(env: &Env, product_id: &String) -> u32 {
+    StorageContract::get_excursion_count(env, product_id)
+}
+
+pub fn put_excursion(env: &Env, product_id: &String, index: u32, excursion: &Excursion) {
+    StorageContract::put_excursion(env, product_id, index, excursion)
+}
+
+pub fn get_timestamp_windows(env: &Env) -> Option<TimestampWindows> {
+    StorageContract::get_timestamp_windows(env)
+}
+
+pub fn set_timestamp_windows(env: &Env, windows: &TimestampWindows) {
+    StorageContract::set_timestamp_windows(env, windows)
+}
+
+pub fn get_event_type_registry(env: &Env) -> Option<Address> {
+    StorageContract::get_event_type_registry(env)
+}
+
+pub fn set_event_type_registry(env: &Env, event_types: &Address) {
+    StorageContract::set_event_type_registry(env, event_types)
+}
+
+pub fn get_sequence_mark(env: &Env, product_id: &String) -> Option<SequenceMark> {
+    StorageContract::get_sequence_mark(env, product_id)
+}
+
+pub fn set_sequence_mark(env: &Env, product_id: &String, mark: &SequenceMark) {
+    StorageContract::set_sequence_mark(env, product_id, mark)
+}
+
+pub fn get_event_sequence(env: &Env, category: &String) -> Option<EventSequence> {
+    StorageContract::get_event_sequence(env, category)
+}
+
+pub fn set_event_sequence(env: &Env, category: &String, sequence: &EventSequence) {
+    StorageContract::set_event_sequence(env, category, sequence)
+}
+
+pub fn remove_event_sequence(env: &Env, category: &String) {
+    StorageContract::remove_event_sequence(env, category)
+}
+
+pub fn append_event_amendment(env: &Env, amendment: &EventAmendment) {
+    StorageContract::append_event_amendment(env, amendment)
+}
+
+pub fn get_event_amendments(env: &Env, event_id: u64) -> Vec<EventAmendment> {
+    StorageContract::get_event_amendments(env, event_id)
+}
+
+pub fn get_event_amendment_count(env: &Env, event_id: u64) -> u32 {
+    StorageContract::get_event_amendment_count(env, event_id)
+}
+
 pub fn put_event(env: &Env, event: &TrackingEvent) {
     StorageContract::put_event(env, event)
 }
@@ -132,3 +427,39 @@ pub fn get_active_products(env: &Env) -> u64 {
 pub fn set_active_products(env: &Env, count: u64) {
     StorageContract::set_active_products(env, count)
 }
+
+// ─── Product status ──────────────────────────────────────────────────────────
+
+pub fn get_status_count(env: &Env, status: ProductStatus) -> u64 {
+    StorageContract::get_status_count(env, status)
+}
+
+pub fn set_status_count(env: &Env, status: ProductStatus, count: u64) {
+    StorageContract::set_status_count(env, status, count)
+}
+
+pub fn get_status_transition(env: &Env, from: ProductStatus, to: ProductStatus) -> Option<bool> {
+    StorageContract::get_status_transition(env, from, to)
+}
+
+pub fn set_status_transition(env: &Env, from: ProductStatus, to: ProductStatus, allowed: bool) {
+    StorageContract::set_status_transition(env, from, to, allowed)
+}
+
+pub fn get_event_status(env: &Env, event_type: &Symbol) -> Option<Option<ProductStatus>> {
+    StorageContract::get_event_status(env, event_type)
+}
+
+pub fn set_event_status(env: &Env, event_type: &Symbol, status: &Option<ProductStatus>) {
+    StorageContract::set_event_status(env, event_type, status)
+}
+
+// ─── TTL bumps ──────────────────────────────────────────────────────────────
+
+pub fn extend_product(env: &Env, product: &Product, offset: u64, limit: u64) -> bool {
+    StorageContract::extend_product(env, product, offset, limit)
+}
+
+pub fn extend_product_events(env: &Env, product_id: &String, offset: u64, limit: u64) -> bool {
+    StorageContract::extend_product_events(env, product_id, offset, limit)
+}
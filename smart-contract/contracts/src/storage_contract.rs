@@ -1,10 +1,79 @@
-use soroban_sdk::{Address, Env, String, Symbol, Vec};
+use soroban_sdk::{Address, BytesN, Env, IntoVal, String, Symbol, TryFromVal, Val, Vec};
 
-use crate::types::{DataKey, Product, TrackingEvent};
+use crate::types::{
+    Certification, DataKey, EventAmendment, EventDataKey, EventSequence, Excursion, LifecycleEntry,
+    Product, ProductAlias, ProductRevision, ProductStatus, Recall, SequenceMark, ThresholdPolicy,
+    TimestampWindows, TrackingEvent,
+};
 
 pub struct StorageContract;
 
 impl StorageContract {
+    // --- TTL policy ---
+    // Entries are extended whenever they are written or read, so anything
+    // still in use stays live. Wiring and config keys live in instance
+    // storage, which shares the contract instance's TTL.
+    pub const DAY_IN_LEDGERS: u32 = 17_280;
+    pub const INSTANCE_BUMP_AMOUNT: u32 = 7 * Self::DAY_IN_LEDGERS;
+    pub const INSTANCE_LIFETIME_THRESHOLD: u32 = Self::INSTANCE_BUMP_AMOUNT - Self::DAY_IN_LEDGERS;
+    pub const PERSISTENT_BUMP_AMOUNT: u32 = 30 * Self::DAY_IN_LEDGERS;
+    pub const PERSISTENT_LIFETIME_THRESHOLD: u32 =
+        Self::PERSISTENT_BUMP_AMOUNT - Self::DAY_IN_LEDGERS;
+
+    pub fn extend_instance(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(Self::INSTANCE_LIFETIME_THRESHOLD, Self::INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Extend a persistent entry if it exists.
+    pub fn extend_persistent<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+        if env.storage().persistent().has(key) {
+            env.storage().persistent().extend_ttl(
+                key,
+                Self::PERSISTENT_LIFETIME_THRESHOLD,
+                Self::PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+    }
+
+    pub fn read_persistent<K: IntoVal<Env, Val>, V: TryFromVal<Env, Val>>(
+        env: &Env,
+        key: &K,
+    ) -> Option<V> {
+        let value = env.storage().persistent().get(key);
+        if value.is_some() {
+            env.storage().persistent().extend_ttl(
+                key,
+                Self::PERSISTENT_LIFETIME_THRESHOLD,
+                Self::PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+        value
+    }
+
+    pub fn write_persistent<K: IntoVal<Env, Val>, V: IntoVal<Env, Val>>(env: &Env, key: &K, value: &V) {
+        env.storage().persistent().set(key, value);
+        env.storage().persistent().extend_ttl(
+            key,
+            Self::PERSISTENT_LIFETIME_THRESHOLD,
+            Self::PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    pub fn read_instance<K: IntoVal<Env, Val>, V: TryFromVal<Env, Val>>(
+        env: &Env,
+        key: &K,
+    ) -> Option<V> {
+        Self::extend_instance(env);
+        env.storage().instance().get(key)
+    }
+
+    pub fn write_instance<K: IntoVal<Env, Val>, V: IntoVal<Env, Val>>(env: &Env, key: &K, value: &V) {
+        env.storage().instance().set(key, value);
+        Self::extend_instance(env);
+    }
+
     pub fn auth_contract_key() -> DataKey {
         DataKey::AuthContract
     }
@@ -13,10 +82,158 @@ impl StorageContract {
         DataKey::Product(product_id.clone())
     }
 
+    pub fn product_revision_key(product_id: &String, revision: u32) -> DataKey {
+        DataKey::ProductRevision(product_id.clone(), revision)
+    }
+
+    pub fn product_revision_count_key(product_id: &String) -> DataKey {
+        DataKey::ProductRevisionCount(product_id.clone())
+    }
+
+    pub fn product_lifecycle_key(product_id: &String, index: u64) -> DataKey {
+        DataKey::ProductLifecycle(product_id.clone(), index)
+    }
+
+    pub fn product_lifecycle_count_key(product_id: &String) -> DataKey {
+        DataKey::ProductLifecycleCount(product_id.clone())
+    }
+
+    pub fn product_certification_key(product_id: &String, id: u32) -> DataKey {
+        DataKey::ProductCertification(product_id.clone(), id)
+    }
+
+    pub fn product_certification_count_key(product_id: &String) -> DataKey {
+        DataKey::ProductCertificationCount(product_id.clone())
+    }
+
+    pub fn alias_key(alias: &ProductAlias) -> DataKey {
+        DataKey::Alias(alias.namespace.clone(), alias.value.clone())
+    }
+
+    pub fn product_aliases_key(product_id: &String) -> DataKey {
+        DataKey::ProductAliases(product_id.clone())
+    }
+
+    pub fn owner_product_index_key(owner: &Address, index: u64) -> DataKey {
+        DataKey::OwnerProductIndex(owner.clone(), index)
+    }
+
+    pub fn owner_product_count_key(owner: &Address) -> DataKey {
+        DataKey::OwnerProductCount(owner.clone())
+    }
+
+    pub fn owner_product_position_key(product_id: &String) -> DataKey {
+        DataKey::OwnerProductPosition(product_id.clone())
+    }
+
+    pub fn category_product_index_key(category: &String, active_only: bool, index: u64) -> DataKey {
+        DataKey::CategoryProductIndex(category.clone(), active_only, index)
+    }
+
+    pub fn category_product_count_key(category: &String, active_only: bool) -> DataKey {
+        DataKey::CategoryProductCount(category.clone(), active_only)
+    }
+
+    pub fn category_product_position_key(product_id: &String, active_only: bool) -> DataKey {
+        DataKey::CategoryProductPosition(product_id.clone(), active_only)
+    }
+
+    pub fn tag_product_index_key(tag: &String, active_only: bool, index: u64) -> DataKey {
+        DataKey::TagProductIndex(tag.clone(), active_only, index)
+    }
+
+    pub fn tag_product_count_key(tag: &String, active_only: bool) -> DataKey {
+        DataKey::TagProductCount(tag.clone(), active_only)
+    }
+
+    pub fn tag_product_position_key(tag: &String, product_id: &String, active_only: bool) -> DataKey {
+        DataKey::TagProductPosition(tag.clone(), product_id.clone(), active_only)
+    }
+
+    pub fn product_components_key(product_id: &String) -> DataKey {
+        DataKey::ProductComponents(product_id.clone())
+    }
+
+    pub fn product_parent_key(product_id: &String) -> DataKey {
+        DataKey::ProductParent(product_id.clone())
+    }
+
+    pub fn product_sources_key(product_id: &String) -> DataKey {
+        DataKey::ProductSources(product_id.clone())
+    }
+
+    pub fn derived_products_key(product_id: &String) -> DataKey {
+        DataKey::DerivedProducts(product_id.clone())
+    }
+
+    pub fn product_recall_key(product_id: &String) -> DataKey {
+        DataKey::ProductRecall(product_id.clone())
+    }
+
+    pub fn recall_index_key(index: u64) -> DataKey {
+        DataKey::RecallIndex(index)
+    }
+
+    pub fn recall_count_key() -> DataKey {
+        DataKey::RecallCount
+    }
+
+    pub fn recall_position_key(product_id: &String) -> DataKey {
+        DataKey::RecallPosition(product_id.clone())
+    }
+
+    pub fn product_index_key(index: u64) -> DataKey {
+        DataKey::ProductIndex(index)
+    }
+
+    pub fn product_index_count_key() -> DataKey {
+        DataKey::ProductIndexCount
+    }
+
     pub fn product_event_ids_key(product_id: &String) -> DataKey {
         DataKey::ProductEventIds(product_id.clone())
     }
 
+    pub fn product_event_head_key(product_id: &String) -> DataKey {
+        DataKey::ProductEventHead(product_id.clone())
+    }
+
+    pub fn event_sequence_key(category: &String) -> EventDataKey {
+        EventDataKey::Sequence(category.clone())
+    }
+
+    pub fn event_amendment_key(event_id: u64, index: u32) -> EventDataKey {
+        EventDataKey::Amendment(event_id, index)
+    }
+
+    pub fn event_amendment_count_key(event_id: u64) -> EventDataKey {
+        EventDataKey::AmendmentCount(event_id)
+    }
+
+    pub fn aggregate_products_key(event_id: u64) -> EventDataKey {
+        EventDataKey::AggregateProducts(event_id)
+    }
+
+    pub fn aggregate_link_key(event_id: u64, product_id: &String) -> EventDataKey {
+        EventDataKey::AggregateLink(event_id, product_id.clone())
+    }
+
+    pub fn sequence_mark_key(product_id: &String) -> EventDataKey {
+        EventDataKey::SequenceMark(product_id.clone())
+    }
+
+    pub fn threshold_policy_key(product_id: &String) -> EventDataKey {
+        EventDataKey::ThresholdPolicy(product_id.clone())
+    }
+
+    pub fn excursion_key(product_id: &String, index: u32) -> EventDataKey {
+        EventDataKey::Excursion(product_id.clone(), index)
+    }
+
+    pub fn excursion_count_key(product_id: &String) -> EventDataKey {
+        EventDataKey::ExcursionCount(product_id.clone())
+    }
+
     pub fn event_key(event_id: u64) -> DataKey {
         DataKey::Event(event_id)
     }
@@ -33,6 +250,10 @@ impl StorageContract {
         DataKey::EventTypeIndex(product_id.clone(), event_type.clone(), index)
     }
 
+    pub fn event_type_position_key(event_id: u64) -> EventDataKey {
+        EventDataKey::EventTypePosition(event_id)
+    }
+
     pub fn auth_key(product_id: &String, actor: &Address) -> DataKey {
         DataKey::Auth(product_id.clone(), actor.clone())
     }
@@ -53,14 +274,24 @@ impl StorageContract {
         DataKey::ActiveProducts
     }
 
+    pub fn status_count_key(status: ProductStatus) -> DataKey {
+        DataKey::StatusCount(status)
+    }
+
+    pub fn status_transition_key(from: ProductStatus, to: ProductStatus) -> DataKey {
+        DataKey::StatusTransition(from, to)
+    }
+
+    pub fn event_status_key(event_type: &Symbol) -> DataKey {
+        DataKey::EventStatus(event_type.clone())
+    }
+
     pub fn get_auth_contract(env: &Env) -> Option<Address> {
-        env.storage().persistent().get(&Self::auth_contract_key())
+        Self::read_instance(env, &Self::auth_contract_key())
     }
 
     pub fn set_auth_contract(env: &Env, address: &Address) {
-        env.storage()
-            .persistent()
-            .set(&Self::auth_contract_key(), address);
+        Self::write_instance(env, &Self::auth_contract_key(), address);
     }
 
     pub fn has_product(env: &Env, product_id: &String) -> bool {
@@ -68,27 +299,404 @@ impl StorageContract {
     }
 
     pub fn put_product(env: &Env, product: &Product) {
-        env.storage()
-            .persistent()
-            .set(&Self::product_key(&product.id), product);
+        Self::write_persistent(env, &Self::product_key(&product.id), product);
     }
 
     pub fn get_product(env: &Env, product_id: &String) -> Option<Product> {
-        env.storage()
-            .persistent()
-            .get(&Self::product_key(product_id))
+        Self::read_persistent(env, &Self::product_key(product_id))
+    }
+
+    pub fn put_product_revision(env: &Env, revision: &ProductRevision) {
+        Self::write_persistent(
+            env,
+            &Self::product_revision_key(&revision.product.id, revision.revision),
+            revision,
+        );
+        Self::write_persistent(
+            env,
+            &Self::product_revision_count_key(&revision.product.id),
+            &(revision.revision + 1),
+        );
+    }
+
+    pub fn get_product_revision(
+        env: &Env,
+        product_id: &String,
+        revision: u32,
+    ) -> Option<ProductRevision> {
+        Self::read_persistent(env, &Self::product_revision_key(product_id, revision))
+    }
+
+    /// Number of archived revisions, which is also the revision number of
+    /// the product's current state.
+    pub fn get_product_revision_count(env: &Env, product_id: &String) -> u32 {
+        Self::read_persistent(env, &Self::product_revision_count_key(product_id)).unwrap_or(0)
+    }
+
+    /// Store a certification under its ID, growing the product's
+    /// certification count to cover it.
+    pub fn put_certification(env: &Env, product_id: &String, certification: &Certification) {
+        Self::write_persistent(
+            env,
+            &Self::product_certification_key(product_id, certification.id),
+            certification,
+        );
+        if certification.id >= Self::get_certification_count(env, product_id) {
+            Self::write_persistent(
+                env,
+                &Self::product_certification_count_key(product_id),
+                &(certification.id + 1),
+            );
+        }
+    }
+
+    pub fn get_certification(env: &Env, product_id: &String, id: u32) -> Option<Certification> {
+        Self::read_persistent(env, &Self::product_certification_key(product_id, id))
+    }
+
+    pub fn get_certification_count(env: &Env, product_id: &String) -> u32 {
+        Self::read_persistent(env, &Self::product_certification_count_key(product_id)).unwrap_or(0)
+    }
+
+    /// Product an alias points to, if it is in use.
+    pub fn get_alias_product(env: &Env, alias: &ProductAlias) -> Option<String> {
+        Self::read_persistent(env, &Self::alias_key(alias))
+    }
+
+    pub fn get_product_aliases(env: &Env, product_id: &String) -> Vec<ProductAlias> {
+        Self::read_persistent(env, &Self::product_aliases_key(product_id)).unwrap_or(Vec::new(env))
+    }
+
+    /// Point an alias at a product and add it to the product's alias list.
+    pub fn add_alias(env: &Env, product_id: &String, alias: &ProductAlias) {
+        Self::write_persistent(env, &Self::alias_key(alias), product_id);
+
+        let mut aliases = Self::get_product_aliases(env, product_id);
+        aliases.push_back(alias.clone());
+        Self::write_persistent(env, &Self::product_aliases_key(product_id), &aliases);
+    }
+
+    /// Free an alias and drop it from the product's alias list.
+    pub fn remove_alias(env: &Env, product_id: &String, alias: &ProductAlias) {
+        env.storage().persistent().remove(&Self::alias_key(alias));
+
+        let mut aliases = Self::get_product_aliases(env, product_id);
+        if let Some(i) = aliases.first_index_of(alias) {
+            aliases.remove(i);
+        }
+        Self::write_persistent(env, &Self::product_aliases_key(product_id), &aliases);
+    }
+
+    pub fn append_lifecycle_entry(env: &Env, product_id: &String, entry: &LifecycleEntry) {
+        let count = Self::get_lifecycle_count(env, product_id);
+        Self::write_persistent(env, &Self::product_lifecycle_key(product_id, count), entry);
+        Self::write_persistent(env, &Self::product_lifecycle_count_key(product_id), &(count + 1));
+    }
+
+    pub fn get_lifecycle_entries(
+        env: &Env,
+        product_id: &String,
+        offset: u64,
+        limit: u64,
+    ) -> Vec<LifecycleEntry> {
+        Self::id_list_page(
+            env,
+            |i| Self::product_lifecycle_key(product_id, i),
+            &Self::product_lifecycle_count_key(product_id),
+            offset,
+            limit,
+        )
+    }
+
+    pub fn get_lifecycle_count(env: &Env, product_id: &String) -> u64 {
+        Self::read_persistent(env, &Self::product_lifecycle_count_key(product_id)).unwrap_or(0)
+    }
+
+    // Owner, category and tag indexes are dense lists of product IDs, one
+    // entry per key, with a position key per member so removal is O(1).
+
+    fn id_list_add(
+        env: &Env,
+        entry_key: impl Fn(u64) -> DataKey,
+        count_key: &DataKey,
+        position_key: &DataKey,
+        product_id: &String,
+    ) {
+        if env.storage().persistent().has(position_key) {
+            return;
+        }
+
+        let count: u64 = Self::read_persistent(env, count_key).unwrap_or(0);
+        Self::write_persistent(env, &entry_key(count), product_id);
+        Self::write_persistent(env, position_key, &count);
+        Self::write_persistent(env, count_key, &(count + 1));
+    }
+
+    /// Remove a product ID by moving the list's last entry into its slot.
+    fn id_list_remove(
+        env: &Env,
+        entry_key: impl Fn(u64) -> DataKey,
+        count_key: &DataKey,
+        position_key: impl Fn(&String) -> DataKey,
+        product_id: &String,
+    ) {
+        let own_position_key = position_key(product_id);
+        let position: u64 = match Self::read_persistent(env, &own_position_key) {
+            Some(p) => p,
+            None => return,
+        };
+
+        let count: u64 = Self::read_persistent(env, count_key).unwrap_or(0);
+        if count == 0 {
+            return;
+        }
+        let last = count - 1;
+
+        if position != last {
+            if let Some(moved) = Self::read_persistent::<DataKey, String>(env, &entry_key(last)) {
+                Self::write_persistent(env, &entry_key(position), &moved);
+                Self::write_persistent(env, &position_key(&moved), &position);
+            }
+        }
+
+        env.storage().persistent().remove(&entry_key(last));
+        env.storage().persistent().remove(&own_position_key);
+        Self::write_persistent(env, count_key, &last);
+    }
+
+    /// Read a page of a dense list: the product ID indexes, or any other
+    /// list kept one entry per key with a count.
+    fn id_list_page<V: IntoVal<Env, Val> + TryFromVal<Env, Val>>(
+        env: &Env,
+        entry_key: impl Fn(u64) -> DataKey,
+        count_key: &DataKey,
+        offset: u64,
+        limit: u64,
+    ) -> Vec<V> {
+        let mut result = Vec::new(env);
+
+        if limit == 0 || limit > 1000 {
+            return result;
+        }
+
+        let total: u64 = Self::read_persistent(env, count_key).unwrap_or(0);
+        if offset >= total {
+            return result;
+        }
+
+        let end = (offset + limit).min(total);
+        for i in offset..end {
+            if let Some(entry) = Self::read_persistent(env, &entry_key(i)) {
+                result.push_back(entry);
+            }
+        }
+
+        result
+    }
+
+    pub fn add_owner_product(env: &Env, owner: &Address, product_id: &String) {
+        Self::id_list_add(
+            env,
+            |i| Self::owner_product_index_key(owner, i),
+            &Self::owner_product_count_key(owner),
+            &Self::owner_product_position_key(product_id),
+            product_id,
+        );
+    }
+
+    pub fn remove_owner_product(env: &Env, owner: &Address, product_id: &String) {
+        Self::id_list_remove(
+            env,
+            |i| Self::owner_product_index_key(owner, i),
+            &Self::owner_product_count_key(owner),
+            Self::owner_product_position_key,
+            product_id,
+        );
+    }
+
+    pub fn get_owner_product_ids(
+        env: &Env,
+        owner: &Address,
+        offset: u64,
+        limit: u64,
+    ) -> Vec<String> {
+        Self::id_list_page(
+            env,
+            |i| Self::owner_product_index_key(owner, i),
+            &Self::owner_product_count_key(owner),
+            offset,
+            limit,
+        )
+    }
+
+    pub fn get_owner_product_count(env: &Env, owner: &Address) -> u64 {
+        Self::read_persistent(env, &Self::owner_product_count_key(owner)).unwrap_or(0)
+    }
+
+    pub fn add_category_product(env: &Env, category: &String, active_only: bool, product_id: &String) {
+        Self::id_list_add(
+            env,
+            |i| Self::category_product_index_key(category, active_only, i),
+            &Self::category_product_count_key(category, active_only),
+            &Self::category_product_position_key(product_id, active_only),
+            product_id,
+        );
+    }
+
+    pub fn remove_category_product(
+        env: &Env,
+        category: &String,
+        active_only: bool,
+        product_id: &String,
+    ) {
+        Self::id_list_remove(
+            env,
+            |i| Self::category_product_index_key(category, active_only, i),
+            &Self::category_product_count_key(category, active_only),
+            |id| Self::category_product_position_key(id, active_only),
+            product_id,
+        );
+    }
+
+    pub fn get_category_product_ids(
+        env: &Env,
+        category: &String,
+        active_only: bool,
+        offset: u64,
+        limit: u64,
+    ) -> Vec<String> {
+        Self::id_list_page(
+            env,
+            |i| Self::category_product_index_key(category, active_only, i),
+            &Self::category_product_count_key(category, active_only),
+            offset,
+            limit,
+        )
+    }
+
+    pub fn get_category_product_count(env: &Env, category: &String, active_only: bool) -> u64 {
+        Self::read_persistent(env, &Self::category_product_count_key(category, active_only))
+            .unwrap_or(0)
+    }
+
+    pub fn add_tag_product(env: &Env, tag: &String, active_only: bool, product_id: &String) {
+        Self::id_list_add(
+            env,
+            |i| Self::tag_product_index_key(tag, active_only, i),
+            &Self::tag_product_count_key(tag, active_only),
+            &Self::tag_product_position_key(tag, product_id, active_only),
+            product_id,
+        );
+    }
+
+    pub fn remove_tag_product(env: &Env, tag: &String, active_only: bool, product_id: &String) {
+        Self::id_list_remove(
+            env,
+            |i| Self::tag_product_index_key(tag, active_only, i),
+            &Self::tag_product_count_key(tag, active_only),
+            |id| Self::tag_product_position_key(tag, id, active_only),
+            product_id,
+        );
+    }
+
+    pub fn get_tag_product_ids(
+        env: &Env,
+        tag: &String,
+        active_only: bool,
+        offset: u64,
+        limit: u64,
+    ) -> Vec<String> {
+        Self::id_list_page(
+            env,
+            |i| Self::tag_product_index_key(tag, active_only, i),
+            &Self::tag_product_count_key(tag, active_only),
+            offset,
+            limit,
+        )
+    }
+
+    pub fn get_tag_product_count(env: &Env, tag: &String, active_only: bool) -> u64 {
+        Self::read_persistent(env, &Self::tag_product_count_key(tag, active_only)).unwrap_or(0)
+    }
+
+    pub fn put_product_components(env: &Env, product_id: &String, components: &Vec<String>) {
+        Self::write_persistent(env, &Self::product_components_key(product_id), components);
+    }
+
+    pub fn get_product_components(env: &Env, product_id: &String) -> Vec<String> {
+        Self::read_persistent(env, &Self::product_components_key(product_id))
+            .unwrap_or(Vec::new(env))
+    }
+
+    pub fn set_product_parent(env: &Env, product_id: &String, parent_id: &String) {
+        Self::write_persistent(env, &Self::product_parent_key(product_id), parent_id);
+    }
+
+    pub fn get_product_parent(env: &Env, product_id: &String) -> Option<String> {
+        Self::read_persistent(env, &Self::product_parent_key(product_id))
+    }
+
+    pub fn put_product_sources(env: &Env, product_id: &String, sources: &Vec<String>) {
+        Self::write_persistent(env, &Self::product_sources_key(product_id), sources);
+    }
+
+    pub fn get_product_sources(env: &Env, product_id: &String) -> Vec<String> {
+        Self::read_persistent(env, &Self::product_sources_key(product_id)).unwrap_or(Vec::new(env))
+    }
+
+    pub fn put_derived_products(env: &Env, product_id: &String, derived: &Vec<String>) {
+        Self::write_persistent(env, &Self::derived_products_key(product_id), derived);
+    }
+
+    pub fn get_derived_products(env: &Env, product_id: &String) -> Vec<String> {
+        Self::read_persistent(env, &Self::derived_products_key(product_id)).unwrap_or(Vec::new(env))
+    }
+
+    /// Store a product's recall and add it to the recalled-products list.
+    pub fn put_product_recall(env: &Env, recall: &Recall) {
+        Self::write_persistent(env, &Self::product_recall_key(&recall.product_id), recall);
+        Self::id_list_add(
+            env,
+            Self::recall_index_key,
+            &Self::recall_count_key(),
+            &Self::recall_position_key(&recall.product_id),
+            &recall.product_id,
+        );
+    }
+
+    pub fn get_product_recall(env: &Env, product_id: &String) -> Option<Recall> {
+        Self::read_persistent(env, &Self::product_recall_key(product_id))
+    }
+
+    pub fn get_recalled_product_ids(env: &Env, offset: u64, limit: u64) -> Vec<String> {
+        Self::id_list_page(env, Self::recall_index_key, &Self::recall_count_key(), offset, limit)
+    }
+
+    pub fn get_recall_count(env: &Env) -> u64 {
+        Self::read_persistent(env, &Self::recall_count_key()).unwrap_or(0)
+    }
+
+    /// Append a product ID to the global registration-ordered index.
+    pub fn append_product_index(env: &Env, product_id: &String) {
+        let count = Self::get_product_index_count(env);
+        Self::write_persistent(env, &Self::product_index_key(count), product_id);
+        Self::write_persistent(env, &Self::product_index_count_key(), &(count + 1));
+    }
+
+    pub fn get_product_id_at(env: &Env, index: u64) -> Option<String> {
+        Self::read_persistent(env, &Self::product_index_key(index))
+    }
+
+    pub fn get_product_index_count(env: &Env) -> u64 {
+        Self::read_persistent(env, &Self::product_index_count_key()).unwrap_or(0)
     }
 
     pub fn put_product_event_ids(env: &Env, product_id: &String, ids: &Vec<u64>) {
-        env.storage()
-            .persistent()
-            .set(&Self::product_event_ids_key(product_id), ids);
+        Self::write_persistent(env, &Self::product_event_ids_key(product_id), ids);
     }
 
     pub fn get_product_event_ids(env: &Env, product_id: &String) -> Vec<u64> {
-        env.storage()
-            .persistent()
-            .get(&Self::product_event_ids_key(product_id))
+        Self::read_persistent(env, &Self::product_event_ids_key(product_id))
             .unwrap_or(Vec::new(env))
     }
 
@@ -125,40 +733,154 @@ impl StorageContract {
         result
     }
 
-    pub fn put_event(env: &Env, event: &TrackingEvent) {
+    pub fn get_product_event_head(env: &Env, product_id: &String) -> Option<BytesN<32>> {
+        Self::read_persistent(env, &Self::product_event_head_key(product_id))
+    }
+
+    pub fn set_product_event_head(env: &Env, product_id: &String, hash: &BytesN<32>) {
+        Self::write_persistent(env, &Self::product_event_head_key(product_id), hash);
+    }
+
+    /// Products an aggregate event was recorded for; empty for an event
+    /// recorded for a single product.
+    pub fn get_aggregate_products(env: &Env, event_id: u64) -> Vec<String> {
+        Self::read_persistent(env, &Self::aggregate_products_key(event_id)).unwrap_or(Vec::new(env))
+    }
+
+    pub fn set_aggregate_products(env: &Env, event_id: u64, product_ids: &Vec<String>) {
+        Self::write_persistent(env, &Self::aggregate_products_key(event_id), product_ids);
+    }
+
+    /// Hash an aggregate event links to in the chain of a product other
+    /// than the one named in its record.
+    pub fn get_aggregate_link(env: &Env, event_id: u64, product_id: &String) -> Option<BytesN<32>> {
+        Self::read_persistent(env, &Self::aggregate_link_key(event_id, product_id))
+    }
+
+    pub fn set_aggregate_link(env: &Env, event_id: u64, product_id: &String, hash: &BytesN<32>) {
+        Self::write_persistent(env, &Self::aggregate_link_key(event_id, product_id), hash);
+    }
+
+    pub fn get_threshold_policy(env: &Env, product_id: &String) -> Option<ThresholdPolicy> {
+        Self::read_persistent(env, &Self::threshold_policy_key(product_id))
+    }
+
+    pub fn set_threshold_policy(env: &Env, product_id: &String, policy: &ThresholdPolicy) {
+        Self::write_persistent(env, &Self::threshold_policy_key(product_id), policy);
+    }
+
+    pub fn get_excursions(env: &Env, product_id: &String) -> Vec<Excursion> {
+        let mut result = Vec::new(env);
+        for i in 0..Self::get_excursion_count(env, product_id) {
+            if let Some(excursion) = Self::read_persistent(env, &Self::excursion_key(product_id, i)) {
+                result.push_back(excursion);
+            }
+        }
+        result
+    }
+
+    pub fn get_excursion(env: &Env, product_id: &String, index: u32) -> Option<Excursion> {
+        Self::read_persistent(env, &Self::excursion_key(product_id, index))
+    }
+
+    pub fn get_excursion_count(env: &Env, product_id: &String) -> u32 {
+        Self::read_persistent(env, &Self::excursion_count_key(product_id)).unwrap_or(0)
+    }
+
+    /// Store the product's excursion at `index`, appending it if `index`
+    /// is the current count.
+    pub fn put_excursion(env: &Env, product_id: &String, index: u32, excursion: &Excursion) {
+        Self::write_persistent(env, &Self::excursion_key(product_id, index), excursion);
+        if index == Self::get_excursion_count(env, product_id) {
+            Self::write_persistent(env, &Self::excursion_count_key(product_id), &(index + 1));
+        }
+    }
+
+    pub fn get_timestamp_windows(env: &Env) -> Option<TimestampWindows> {
+        Self::read_instance(env, &EventDataKey::TimestampWindows)
+    }
+
+    pub fn set_timestamp_windows(env: &Env, windows: &TimestampWindows) {
+        Self::write_instance(env, &EventDataKey::TimestampWindows, windows);
+    }
+
+    pub fn get_event_type_registry(env: &Env) -> Option<Address> {
+        Self::read_instance(env, &DataKey::EventTypeRegistry)
+    }
+
+    pub fn set_event_type_registry(env: &Env, event_types: &Address) {
+        Self::write_instance(env, &DataKey::EventTypeRegistry, event_types);
+    }
+
+    pub fn get_sequence_mark(env: &Env, product_id: &String) -> Option<SequenceMark> {
+        Self::read_persistent(env, &Self::sequence_mark_key(product_id))
+    }
+
+    pub fn set_sequence_mark(env: &Env, product_id: &String, mark: &SequenceMark) {
+        Self::write_persistent(env, &Self::sequence_mark_key(product_id), mark);
+    }
+
+    pub fn get_event_sequence(env: &Env, category: &String) -> Option<EventSequence> {
+        Self::read_persistent(env, &Self::event_sequence_key(category))
+    }
+
+    pub fn set_event_sequence(env: &Env, category: &String, sequence: &EventSequence) {
+        Self::write_persistent(env, &Self::event_sequence_key(category), sequence);
+    }
+
+    pub fn remove_event_sequence(env: &Env, category: &String) {
         env.storage()
             .persistent()
-            .set(&Self::event_key(event.event_id), event);
+            .remove(&Self::event_sequence_key(category));
+    }
+
+    pub fn append_event_amendment(env: &Env, amendment: &EventAmendment) {
+        let count = Self::get_event_amendment_count(env, amendment.event_id);
+        Self::write_persistent(env, &Self::event_amendment_key(amendment.event_id, count), amendment);
+        Self::write_persistent(env, &Self::event_amendment_count_key(amendment.event_id), &(count + 1));
+    }
+
+    pub fn get_event_amendments(env: &Env, event_id: u64) -> Vec<EventAmendment> {
+        let mut result = Vec::new(env);
+        for i in 0..Self::get_event_amendment_count(env, event_id) {
+            if let Some(amendment) = Self::read_persistent(env, &Self::event_amendment_key(event_id, i)) {
+                result.push_back(amendment);
+            }
+        }
+        result
+    }
+
+    pub fn get_event_amendment_count(env: &Env, event_id: u64) -> u32 {
+        Self::read_persistent(env, &Self::event_amendment_count_key(event_id)).unwrap_or(0)
+    }
+
+    pub fn put_event(env: &Env, event: &TrackingEvent) {
+        Self::write_persistent(env, &Self::event_key(event.event_id), event);
     }
 
     pub fn get_event(env: &Env, event_id: u64) -> Option<TrackingEvent> {
-        env.storage().persistent().get(&Self::event_key(event_id))
+        Self::read_persistent(env, &Self::event_key(event_id))
     }
 
     pub fn next_event_id(env: &Env) -> u64 {
-        let mut seq: u64 = env
-            .storage()
-            .persistent()
-            .get(&Self::event_seq_key())
-            .unwrap_or(0);
+        let mut seq: u64 = Self::read_persistent(env, &Self::event_seq_key()).unwrap_or(0);
         seq += 1;
-        env.storage()
-            .persistent()
-            .set(&Self::event_seq_key(), &seq);
+        Self::write_persistent(env, &Self::event_seq_key(), &seq);
         seq
     }
 
     pub fn index_event_by_type(env: &Env, product_id: &String, event_type: &Symbol, event_id: u64) {
         let count_key = Self::event_type_count_key(product_id, event_type);
-        let mut count: u64 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let mut count: u64 = Self::read_persistent(env, &count_key).unwrap_or(0);
         
         // Store at 0-based index (count represents next available index)
         let index_key = Self::event_type_index_key(product_id, event_type, count);
-        env.storage().persistent().set(&index_key, &event_id);
+        Self::write_persistent(env, &index_key, &event_id);
+        Self::write_persistent(env, &Self::event_type_position_key(event_id), &count);
         
         // Increment count after storing
         count += 1;
-        env.storage().persistent().set(&count_key, &count);
+        Self::write_persistent(env, &count_key, &count);
     }
 
     pub fn get_event_ids_by_type(
@@ -169,7 +891,7 @@ impl StorageContract {
         limit: u64,
     ) -> Vec<u64> {
         let count_key = Self::event_type_count_key(product_id, event_type);
-        let total: u64 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let total: u64 = Self::read_persistent(env, &count_key).unwrap_or(0);
 
         let mut result = Vec::new(env);
 
@@ -183,11 +905,7 @@ impl StorageContract {
 
         for i in start..end {
             let index_key = Self::event_type_index_key(product_id, event_type, i);
-            if let Some(event_id) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, u64>(&index_key)
-            {
+            if let Some(event_id) = Self::read_persistent::<DataKey, u64>(env, &index_key) {
                 result.push_back(event_id);
             }
         }
@@ -197,14 +915,12 @@ impl StorageContract {
 
     pub fn get_event_count_by_type(env: &Env, product_id: &String, event_type: &Symbol) -> u64 {
         let count_key = Self::event_type_count_key(product_id, event_type);
-        env.storage().persistent().get(&count_key).unwrap_or(0)
+        Self::read_persistent(env, &count_key).unwrap_or(0)
     }
 
     pub fn set_auth(env: &Env, product_id: &String, actor: &Address, value: bool) {
         if value {
-            env.storage()
-                .persistent()
-                .set(&Self::auth_key(product_id, actor), &true);
+            Self::write_persistent(env, &Self::auth_key(product_id, actor), &true);
         } else {
             env.storage()
                 .persistent()
@@ -213,56 +929,187 @@ impl StorageContract {
     }
 
     pub fn is_authorized(env: &Env, product_id: &String, actor: &Address) -> bool {
-        env.storage()
-            .persistent()
-            .get(&Self::auth_key(product_id, actor))
-            .unwrap_or(false)
+        Self::read_persistent(env, &Self::auth_key(product_id, actor)).unwrap_or(false)
     }
 
     pub fn has_admin(env: &Env) -> bool {
-        env.storage().persistent().has(&Self::admin_key())
+        env.storage().instance().has(&Self::admin_key())
     }
 
     pub fn get_admin(env: &Env) -> Option<Address> {
-        env.storage().persistent().get(&Self::admin_key())
+        Self::read_instance(env, &Self::admin_key())
     }
 
     pub fn set_admin(env: &Env, admin: &Address) {
-        env.storage().persistent().set(&Self::admin_key(), admin);
+        Self::write_instance(env, &Self::admin_key(), admin);
     }
 
     pub fn is_paused(env: &Env) -> bool {
-        env.storage().persistent().get(&Self::paused_key()).unwrap_or(false)
+        Self::read_instance(env, &Self::paused_key()).unwrap_or(false)
     }
 
     pub fn set_paused(env: &Env, paused: bool) {
-        env.storage().persistent().set(&Self::paused_key(), &paused);
+        Self::write_instance(env, &Self::paused_key(), &paused);
     }
 
     pub fn get_total_products(env: &Env) -> u64 {
-        env.storage()
-            .instance()
-            .get(&Self::total_products_key())
-            .unwrap_or(0)
+        Self::read_instance(env, &Self::total_products_key()).unwrap_or(0)
     }
 
     pub fn set_total_products(env: &Env, count: u64) {
-        env.storage()
-            .instance()
-            .set(&Self::total_products_key(), &count);
+        Self::write_instance(env, &Self::total_products_key(), &count);
     }
 
     pub fn get_active_products(env: &Env) -> u64 {
-        env.storage()
-            .instance()
-            .get(&Self::active_products_key())
-            .unwrap_or(0)
+        Self::read_instance(env, &Self::active_products_key()).unwrap_or(0)
     }
 
     pub fn set_active_products(env: &Env, count: u64) {
-        env.storage()
-            .instance()
-            .set(&Self::active_products_key(), &count);
+        Self::write_instance(env, &Self::active_products_key(), &count);
+    }
+
+    pub fn get_status_count(env: &Env, status: ProductStatus) -> u64 {
+        Self::read_instance(env, &Self::status_count_key(status)).unwrap_or(0)
+    }
+
+    pub fn set_status_count(env: &Env, status: ProductStatus, count: u64) {
+        Self::write_instance(env, &Self::status_count_key(status), &count);
+    }
+
+    /// Admin override for a status transition; `None` means the built-in
+    /// default applies.
+    pub fn get_status_transition(env: &Env, from: ProductStatus, to: ProductStatus) -> Option<bool> {
+        Self::read_instance(env, &Self::status_transition_key(from, to))
+    }
+
+    pub fn set_status_transition(env: &Env, from: ProductStatus, to: ProductStatus, allowed: bool) {
+        Self::write_instance(env, &Self::status_transition_key(from, to), &allowed);
+    }
+
+    /// Admin override for the status an event type moves a product to.
+    /// `Some(None)` means the event type was explicitly unmapped.
+    pub fn get_event_status(env: &Env, event_type: &Symbol) -> Option<Option<ProductStatus>> {
+        Self::read_instance(env, &Self::event_status_key(event_type))
+    }
+
+    pub fn set_event_status(env: &Env, event_type: &Symbol, status: &Option<ProductStatus>) {
+        Self::write_instance(env, &Self::event_status_key(event_type), status);
+    }
+
+    /// Extend a product and every entry kept for it: revisions, lifecycle
+    /// log, certifications, aliases, composition links, recall and its
+    /// slots in the owner, category and tag indexes. Revisions, lifecycle
+    /// entries and certifications are extended for the page starting at
+    /// `offset`; returns whether any remain past it.
+    pub fn extend_product(env: &Env, product: &Product, offset: u64, limit: u64) -> bool {
+        let id = &product.id;
+        Self::extend_persistent(env, &Self::product_key(id));
+
+        let revisions = Self::get_product_revision_count(env, id) as u64;
+        let mut has_more = Self::extend_window(env, revisions, offset, limit, |r| {
+            Self::product_revision_key(id, r as u32)
+        });
+        let lifecycle = Self::get_lifecycle_count(env, id);
+        has_more |= Self::extend_window(env, lifecycle, offset, limit, |i| {
+            Self::product_lifecycle_key(id, i)
+        });
+        let certifications = Self::get_certification_count(env, id) as u64;
+        has_more |= Self::extend_window(env, certifications, offset, limit, |c| {
+            Self::product_certification_key(id, c as u32)
+        });
+
+        for alias in Self::get_product_aliases(env, id).iter() {
+            Self::extend_persistent(env, &Self::alias_key(&alias));
+        }
+
+        Self::extend_persistent(env, &Self::product_components_key(id));
+        Self::extend_persistent(env, &Self::product_parent_key(id));
+        Self::extend_persistent(env, &Self::product_sources_key(id));
+        Self::extend_persistent(env, &Self::derived_products_key(id));
+        Self::extend_persistent(env, &Self::product_recall_key(id));
+
+        Self::extend_list_slot(env, &Self::owner_product_position_key(id), |i| {
+            Self::owner_product_index_key(&product.owner, i)
+        });
+        for active_only in [false, true] {
+            Self::extend_list_slot(env, &Self::category_product_position_key(id, active_only), |i| {
+                Self::category_product_index_key(&product.category, active_only, i)
+            });
+            for tag in product.tags.iter() {
+                Self::extend_list_slot(
+                    env,
+                    &Self::tag_product_position_key(&tag, id, active_only),
+                    |i| Self::tag_product_index_key(&tag, active_only, i),
+                );
+            }
+        }
+        Self::extend_list_slot(env, &Self::recall_position_key(id), Self::recall_index_key);
+
+        has_more
+    }
+
+    /// Extend an ID list membership: the position key and the entry it
+    /// points to.
+    fn extend_list_slot<K: IntoVal<Env, Val>, E: IntoVal<Env, Val>>(
+        env: &Env,
+        position_key: &K,
+        entry_key: impl Fn(u64) -> E,
+    ) {
+        if let Some(position) = Self::read_persistent::<K, u64>(env, position_key) {
+            Self::extend_persistent(env, &entry_key(position));
+        }
+    }
+
+    /// Extend the entries of a `count`-long list that fall in the page
+    /// starting at `offset`. Returns whether entries remain past the page.
+    fn extend_window<K: IntoVal<Env, Val>>(
+        env: &Env,
+        count: u64,
+        offset: u64,
+        limit: u64,
+        entry_key: impl Fn(u64) -> K,
+    ) -> bool {
+        let end = offset.saturating_add(limit).min(count);
+        for i in offset..end {
+            Self::extend_persistent(env, &entry_key(i));
+        }
+        end < count
+    }
+
+    /// Extend a product's event list and head hash, and for the page of
+    /// its events starting at `offset`, those events with their type
+    /// index entries, amendments and aggregate links. Excursions are paged
+    /// with the same window. Returns whether events or excursions remain
+    /// past the page.
+    pub fn extend_product_events(env: &Env, product_id: &String, offset: u64, limit: u64) -> bool {
+        Self::extend_persistent(env, &Self::product_event_head_key(product_id));
+        Self::extend_persistent(env, &Self::threshold_policy_key(product_id));
+        Self::extend_persistent(env, &Self::sequence_mark_key(product_id));
+        let excursions = Self::get_excursion_count(env, product_id) as u64;
+        let mut has_more = Self::extend_window(env, excursions, offset, limit, |i| {
+            Self::excursion_key(product_id, i as u32)
+        });
+
+        let event_ids = Self::get_product_event_ids(env, product_id);
+        let end = offset.saturating_add(limit).min(event_ids.len() as u64);
+        has_more |= end < event_ids.len() as u64;
+        for i in offset..end {
+            let event_id = event_ids.get_unchecked(i as u32);
+            if let Some(event) = Self::read_persistent::<DataKey, TrackingEvent>(env, &Self::event_key(event_id)) {
+                Self::extend_persistent(env, &Self::event_type_count_key(product_id, &event.event_type));
+                Self::extend_list_slot(env, &Self::event_type_position_key(event_id), |index| {
+                    Self::event_type_index_key(product_id, &event.event_type, index)
+                });
+            }
+            Self::extend_persistent(env, &Self::aggregate_products_key(event_id));
+            Self::extend_persistent(env, &Self::aggregate_link_key(event_id, product_id));
+            let amendments = Self::get_event_amendment_count(env, event_id);
+            for a in 0..amendments {
+                Self::extend_persistent(env, &Self::event_amendment_key(event_id, a));
+            }
+        }
+
+        has_more
     }
 }
 
@@ -272,7 +1119,7 @@ mod test_storage_contract {
     use soroban_sdk::{testutils::Address as _, BytesN, Map};
 
     use crate::contract::ChainLogisticsContract;
-    use crate::types::Origin;
+    use crate::types::{EventDevice, EventPosition, IdScheme, Origin};
 
     #[test]
     fn test_product_put_get() {
@@ -282,6 +1129,7 @@ mod test_storage_contract {
 
         let product = Product {
             id: String::from_str(&env, "P1"),
+            id_scheme: IdScheme::Custom,
             name: String::from_str(&env, "Name"),
             description: String::from_str(&env, "Desc"),
             origin: Origin {
@@ -290,6 +1138,7 @@ mod test_storage_contract {
             owner: owner.clone(),
             created_at: 0,
             active: true,
+            status: ProductStatus::Registered,
             category: String::from_str(&env, "Cat"),
             tags: Vec::new(&env),
             certifications: Vec::new(&env),
@@ -546,6 +1395,59 @@ mod test_storage_contract {
         });
     }
 
+    #[test]
+    fn test_owner_index_remove_keeps_index_dense() {
+        let env = Env::default();
+        let owner = Address::generate(&env);
+        let contract_id = env.register_contract(None, ChainLogisticsContract);
+
+        let p1 = String::from_str(&env, "P1");
+        let p2 = String::from_str(&env, "P2");
+        let p3 = String::from_str(&env, "P3");
+
+        env.as_contract(&contract_id, || {
+            StorageContract::add_owner_product(&env, &owner, &p1);
+            StorageContract::add_owner_product(&env, &owner, &p2);
+            StorageContract::add_owner_product(&env, &owner, &p3);
+
+            // Removing from the middle moves the last entry into the gap
+            StorageContract::remove_owner_product(&env, &owner, &p1);
+            assert_eq!(StorageContract::get_owner_product_count(&env, &owner), 2);
+            let ids = StorageContract::get_owner_product_ids(&env, &owner, 0, 10);
+            assert_eq!(ids.get(0), Some(p3.clone()));
+            assert_eq!(ids.get(1), Some(p2.clone()));
+
+            // Removing an unindexed product is a no-op
+            StorageContract::remove_owner_product(&env, &owner, &p1);
+            assert_eq!(StorageContract::get_owner_product_count(&env, &owner), 2);
+
+            StorageContract::remove_owner_product(&env, &owner, &p2);
+            StorageContract::remove_owner_product(&env, &owner, &p3);
+            assert_eq!(StorageContract::get_owner_product_count(&env, &owner), 0);
+            assert!(StorageContract::get_owner_product_ids(&env, &owner, 0, 10).is_empty());
+        });
+    }
+
+    #[test]
+    fn test_product_index_append() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ChainLogisticsContract);
+
+        env.as_contract(&contract_id, || {
+            assert_eq!(StorageContract::get_product_index_count(&env), 0);
+            assert!(StorageContract::get_product_id_at(&env, 0).is_none());
+
+            StorageContract::append_product_index(&env, &String::from_str(&env, "P1"));
+            StorageContract::append_product_index(&env, &String::from_str(&env, "P2"));
+
+            assert_eq!(StorageContract::get_product_index_count(&env), 2);
+            assert_eq!(
+                StorageContract::get_product_id_at(&env, 1),
+                Some(String::from_str(&env, "P2"))
+            );
+        });
+    }
+
     #[test]
     fn test_counters_roundtrip() {
         let env = Env::default();
@@ -574,11 +1476,16 @@ mod test_storage_contract {
             product_id: String::from_str(&env, "P1"),
             event_type: Symbol::new(&env, "created"),
             location: String::from_str(&env, "Loc"),
+            position: EventPosition::None,
+            readings: Vec::new(&env),
+            device: EventDevice::None,
             data_hash: BytesN::from_array(&env, &[0; 32]),
+            prev_event_hash: BytesN::from_array(&env, &[0; 32]),
             note: String::from_str(&env, "Note"),
             metadata: Map::new(&env),
             actor: owner,
-            timestamp: 0,
+            occurred_at: 0,
+            recorded_at: 0,
         };
 
         env.as_contract(&contract_id, || {
@@ -0,0 +1,104 @@
+use soroban_sdk::{Env, String, Symbol};
+
+use crate::storage;
+use crate::types::{Excursion, SensorUnit, ThresholdPolicy, TrackingEvent};
+
+// Cold-chain checks of the temperature readings on a product's events
+// against the product's threshold policy. Each spell of readings outside
+// the policy range is kept as an excursion, which stays open until a
+// reading is back inside the range.
+
+/// How far a reading lies outside the policy range, 0 if inside.
+fn deviation(policy: &ThresholdPolicy, value_e2: i32) -> i64 {
+    if value_e2 > policy.max_temp_e2 {
+        value_e2 as i64 - policy.max_temp_e2 as i64
+    } else if value_e2 < policy.min_temp_e2 {
+        policy.min_temp_e2 as i64 - value_e2 as i64
+    } else {
+        0
+    }
+}
+
+fn open_excursion(env: &Env, product_id: &String) -> Option<(u32, Excursion)> {
+    let count = storage::get_excursion_count(env, product_id);
+    if count == 0 {
+        return None;
+    }
+    storage::get_excursion(env, product_id, count - 1)
+        .filter(|excursion| excursion.open)
+        .map(|excursion| (count - 1, excursion))
+}
+
+/// Check an event's temperature readings against the product's policy,
+/// opening, extending and closing excursions. Emits `excursion` when one
+/// opens and `excursion_breached` when one outlasts the policy.
+pub fn record_readings(env: &Env, product_id: &String, event: &TrackingEvent) {
+    let policy = match storage::get_threshold_policy(env, product_id) {
+        Some(policy) => policy,
+        None => return,
+    };
+
+    for reading in event.readings.iter() {
+        if reading.unit != SensorUnit::Celsius {
+            continue;
+        }
+        let outside = deviation(&policy, reading.value_e2) > 0;
+
+        let (index, mut excursion) = match open_excursion(env, product_id) {
+            Some(open) => open,
+            None if outside => {
+                let excursion = Excursion {
+                    product_id: product_id.clone(),
+                    start_event_id: event.event_id,
+                    started_at: event.occurred_at,
+                    end_event_id: event.event_id,
+                    ended_at: event.occurred_at,
+                    open: true,
+                    peak_e2: reading.value_e2,
+                    breached: false,
+                };
+                storage::put_excursion(env, product_id, storage::get_excursion_count(env, product_id), &excursion);
+                env.events().publish(
+                    (Symbol::new(env, "excursion"), product_id.clone()),
+                    excursion,
+                );
+                continue;
+            }
+            None => continue,
+        };
+
+        excursion.end_event_id = event.event_id;
+        excursion.ended_at = event.occurred_at;
+        if !outside {
+            excursion.open = false;
+        } else if deviation(&policy, reading.value_e2) > deviation(&policy, excursion.peak_e2) {
+            excursion.peak_e2 = reading.value_e2;
+        }
+
+        let lasted = excursion.ended_at.saturating_sub(excursion.started_at);
+        if !excursion.breached && lasted > policy.max_excursion_secs {
+            excursion.breached = true;
+            env.events().publish(
+                (Symbol::new(env, "excursion_breached"), product_id.clone()),
+                excursion.clone(),
+            );
+        }
+        storage::put_excursion(env, product_id, index, &excursion);
+    }
+}
+
+/// A product is compliant unless one of its excursions has lasted longer
+/// than its policy allows, counting an open excursion up to now.
+pub fn is_compliant(env: &Env, product_id: &String) -> bool {
+    let policy = match storage::get_threshold_policy(env, product_id) {
+        Some(policy) => policy,
+        None => return true,
+    };
+
+    let now = env.ledger().timestamp();
+    storage::get_excursions(env, product_id).iter().all(|excursion| {
+        let overdue = excursion.open
+            && now.saturating_sub(excursion.started_at) > policy.max_excursion_secs;
+        !excursion.breached && !overdue
+    })
+}
@@ -1,160 +1,1414 @@
 #![cfg(test)]
 
 use soroban_sdk::{
-    testutils::Address as _,
-    Address, Env, Map, String, Symbol, Vec,
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, Map, String, Symbol, Vec,
 };
 
 use crate::{
     AuthorizationContract, AuthorizationContractClient,
+    CertifierRegistryContract, CertifierRegistryContractClient,
     ProductRegistryContract, ProductRegistryContractClient,
-    Error, ProductConfig,
+    Error, IdScheme, LifecycleAction, ProductAlias, ProductConfig, ProductStatus, ProductUpdate, RecallSeverity,
 };
+use crate::storage_contract::StorageContract;
 
 // ─── Test helpers ─────────────────────────────────────────────────────────────
 
 fn setup(env: &Env) -> ProductRegistryContractClient {
+    setup_with_auth(env).0
+}
+
+fn setup_with_auth(env: &Env) -> (ProductRegistryContractClient<'_>, AuthorizationContractClient<'_>) {
     let auth_id = env.register_contract(None, AuthorizationContract);
     let contract_id = env.register_contract(None, ProductRegistryContract);
     let client = ProductRegistryContractClient::new(env, &contract_id);
     let auth_client = AuthorizationContractClient::new(env, &auth_id);
 
-    auth_client.configure_initializer(&contract_id);
-    client.configure_auth_contract(&auth_id);
+    auth_client.configure_initializer(&contract_id);
+    client.configure_auth_contract(&auth_id);
+
+    (client, auth_client)
+}
+
+fn register_test_product(
+    env: &Env,
+    client: &ProductRegistryContractClient,
+    owner: &Address,
+) -> String {
+    let id = String::from_str(env, "COFFEE-ETH-001");
+    let config = ProductConfig {
+        id: id.clone(),
+        id_scheme: IdScheme::Custom,
+        name: String::from_str(env, "Organic Coffee Beans"),
+        description: String::from_str(env, "Premium single-origin coffee from Ethiopia"),
+        origin_location: String::from_str(env, "Yirgacheffe, Ethiopia"),
+        category: String::from_str(env, "Coffee"),
+        tags: Vec::new(env),
+        certifications: Vec::new(env),
+        media_hashes: Vec::new(env),
+        custom: Map::new(env),
+    };
+
+    client.register_product(owner, &config);
+    id
+}
+
+/// Wire a certifier registry into the product registry and accredit a
+/// new certifier for `standards`. Returns the certifier and the registry
+/// admin.
+fn setup_certifier(env: &Env, client: &ProductRegistryContractClient, standards: &[&str]) -> (Address, Address) {
+    let certifier_id = env.register_contract(None, CertifierRegistryContract);
+    let certifiers = CertifierRegistryContractClient::new(env, &certifier_id);
+    let admin = Address::generate(env);
+    certifiers.certifier_init(&admin);
+    client.configure_admin(&admin);
+    client.configure_certifier_registry(&admin, &certifier_id);
+
+    let certifier = Address::generate(env);
+    for standard in standards {
+        certifiers.accredit_certifier(&admin, &certifier, &Symbol::new(env, standard), &0, &u64::MAX);
+    }
+    (certifier, admin)
+}
+
+fn batch_config(env: &Env, id: &str) -> ProductConfig {
+    ProductConfig {
+        id: String::from_str(env, id),
+        id_scheme: IdScheme::Custom,
+        name: String::from_str(env, "Name"),
+        description: String::from_str(env, ""),
+        origin_location: String::from_str(env, "Origin"),
+        category: String::from_str(env, "Category"),
+        tags: Vec::new(env),
+        certifications: Vec::new(env),
+        media_hashes: Vec::new(env),
+        custom: Map::new(env),
+    }
+}
+
+fn update_from(client: &ProductRegistryContractClient, id: &String) -> ProductUpdate {
+    let p = client.get_product(id);
+    ProductUpdate {
+        name: p.name,
+        description: p.description,
+        category: p.category,
+        tags: p.tags,
+        certifications: p.certifications,
+        media_hashes: p.media_hashes,
+        custom: p.custom,
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// REGISTRATION TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_register_and_get_product() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+
+    let p = client.get_product(&id);
+    assert_eq!(p.id, id);
+    assert_eq!(p.owner, owner);
+    assert!(p.active, "new products must be active");
+    assert!(p.deactivation_info.is_empty(), "no deactivation info on new product");
+}
+
+#[test]
+fn test_register_increments_stats() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+
+    let stats_before = client.get_stats();
+    assert_eq!(stats_before.total_products, 0);
+    assert_eq!(stats_before.active_products, 0);
+
+    register_test_product(&env, &client, &owner);
+
+    let stats_after = client.get_stats();
+    assert_eq!(stats_after.total_products, 1);
+    assert_eq!(stats_after.active_products, 1);
+}
+
+#[test]
+fn test_duplicate_product_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+
+    let config = ProductConfig {
+        id: id.clone(),
+        id_scheme: IdScheme::Custom,
+        name: String::from_str(&env, "Duplicate"),
+        description: String::from_str(&env, ""),
+        origin_location: String::from_str(&env, "Somewhere"),
+        category: String::from_str(&env, "Other"),
+        tags: Vec::new(&env),
+        certifications: Vec::new(&env),
+        media_hashes: Vec::new(&env),
+        custom: Map::new(&env),
+    };
+
+    let res = client.try_register_product(&owner, &config);
+    assert_eq!(res, Err(Ok(Error::ProductAlreadyExists)));
+}
+
+#[test]
+fn test_register_rejects_empty_id() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let config = ProductConfig {
+        id: String::from_str(&env, ""),
+        id_scheme: IdScheme::Custom,
+        name: String::from_str(&env, "Name"),
+        description: String::from_str(&env, ""),
+        origin_location: String::from_str(&env, "Origin"),
+        category: String::from_str(&env, "Category"),
+        tags: Vec::new(&env),
+        certifications: Vec::new(&env),
+        media_hashes: Vec::new(&env),
+        custom: Map::new(&env),
+    };
+
+    let res = client.try_register_product(&owner, &config);
+    assert_eq!(res, Err(Ok(Error::InvalidProductId)));
+}
+
+#[test]
+fn test_register_rejects_empty_origin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let config = ProductConfig {
+        id: String::from_str(&env, "ID-001"),
+        id_scheme: IdScheme::Custom,
+        name: String::from_str(&env, "Name"),
+        description: String::from_str(&env, ""),
+        origin_location: String::from_str(&env, ""),
+        category: String::from_str(&env, "Category"),
+        tags: Vec::new(&env),
+        certifications: Vec::new(&env),
+        media_hashes: Vec::new(&env),
+        custom: Map::new(&env),
+    };
+
+    let res = client.try_register_product(&owner, &config);
+    assert_eq!(res, Err(Ok(Error::InvalidOrigin)));
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// BATCH REGISTRATION TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_register_products_batch_success() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "P-0"));
+    configs.push_back(batch_config(&env, "P-1"));
+    configs.push_back(batch_config(&env, "P-2"));
+
+    let products = client.register_products_batch(&owner, &configs);
+    assert_eq!(products.len(), 3);
+
+    for i in 0..configs.len() {
+        let p = client.get_product(&configs.get_unchecked(i).id);
+        assert_eq!(p.owner, owner);
+        assert!(p.active);
+    }
+
+    let stats = client.get_stats();
+    assert_eq!(stats.total_products, 3);
+    assert_eq!(stats.active_products, 3);
+}
+
+#[test]
+fn test_register_products_batch_atomic_failure() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "P-0"));
+    configs.push_back(batch_config(&env, "P-1"));
+    configs.push_back(batch_config(&env, ""));
+
+    let res = client.try_register_products_batch(&owner, &configs);
+    assert_eq!(res, Err(Ok(Error::InvalidProductId)));
+
+    // Nothing from the batch should have been written
+    let res = client.try_get_product(&String::from_str(&env, "P-0"));
+    assert_eq!(res, Err(Ok(Error::ProductNotFound)));
+    assert_eq!(client.get_stats().total_products, 0);
+}
+
+#[test]
+fn test_register_products_batch_rejects_empty() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let res = client.try_register_products_batch(&owner, &Vec::new(&env));
+    assert_eq!(res, Err(Ok(Error::EmptyBatch)));
+}
+
+#[test]
+fn test_register_products_batch_rejects_too_large() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let mut configs = Vec::new(&env);
+    for _ in 0..101 {
+        configs.push_back(batch_config(&env, "P"));
+    }
+
+    let res = client.try_register_products_batch(&owner, &configs);
+    assert_eq!(res, Err(Ok(Error::BatchTooLarge)));
+}
+
+#[test]
+fn test_register_products_batch_rejects_duplicate_in_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "P-0"));
+    configs.push_back(batch_config(&env, "P-1"));
+    configs.push_back(batch_config(&env, "P-0"));
+
+    let res = client.try_register_products_batch(&owner, &configs);
+    assert_eq!(res, Err(Ok(Error::DuplicateProductIdInBatch)));
+}
+
+#[test]
+fn test_register_products_batch_rejects_existing_product() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "P-0"));
+    configs.push_back(ProductConfig {
+        id,
+        ..batch_config(&env, "")
+    });
+
+    let res = client.try_register_products_batch(&owner, &configs);
+    assert_eq!(res, Err(Ok(Error::ProductAlreadyExists)));
+    assert_eq!(client.get_stats().total_products, 1);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// GS1 IDENTIFIER TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+fn gs1_config(env: &Env, id: &str, scheme: IdScheme) -> ProductConfig {
+    ProductConfig {
+        id_scheme: scheme,
+        ..batch_config(env, id)
+    }
+}
+
+#[test]
+fn test_register_gs1_identifiers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let owner = Address::generate(&env);
+
+    let p = client.register_product(&owner, &gs1_config(&env, "4006381333931", IdScheme::Gtin));
+    assert_eq!(p.id_scheme, IdScheme::Gtin);
+    client.register_product(&owner, &gs1_config(&env, "106141411234567897", IdScheme::Sscc));
+    client.register_product(&owner, &gs1_config(&env, "(01)09506000134352(21)LOT7-0001", IdScheme::Sgtin));
+
+    let res = client.try_register_product(&owner, &gs1_config(&env, "4006381333932", IdScheme::Gtin));
+    assert_eq!(res, Err(Ok(Error::InvalidIdentifier)));
+
+    let res = client.try_register_product(&owner, &gs1_config(&env, "106141411234567890", IdScheme::Sscc));
+    assert_eq!(res, Err(Ok(Error::InvalidIdentifier)));
+
+    let res = client.try_register_product(&owner, &gs1_config(&env, "09506000134352-LOT7", IdScheme::Sgtin));
+    assert_eq!(res, Err(Ok(Error::InvalidIdentifier)));
+
+    // Free-form IDs are still accepted without a scheme
+    client.register_product(&owner, &batch_config(&env, "4006381333932"));
+}
+
+#[test]
+fn test_get_product_by_gtin_serial() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let owner = Address::generate(&env);
+
+    let sgtin = "(01)04006381333931(21)SN-42";
+    client.register_product(&owner, &gs1_config(&env, sgtin, IdScheme::Sgtin));
+
+    // GTIN-13 and GTIN-14 forms find the same product
+    let by_gtin13 = client.get_product_by_gtin_serial(
+        &String::from_str(&env, "4006381333931"),
+        &String::from_str(&env, "SN-42"),
+    );
+    assert_eq!(by_gtin13.id, String::from_str(&env, sgtin));
+    let by_gtin14 = client.get_product_by_gtin_serial(
+        &String::from_str(&env, "04006381333931"),
+        &String::from_str(&env, "SN-42"),
+    );
+    assert_eq!(by_gtin14, by_gtin13);
+
+    let res = client.try_get_product_by_gtin_serial(
+        &String::from_str(&env, "4006381333931"),
+        &String::from_str(&env, "SN-43"),
+    );
+    assert_eq!(res, Err(Ok(Error::ProductNotFound)));
+
+    let res = client.try_get_product_by_gtin_serial(
+        &String::from_str(&env, "4006381333930"),
+        &String::from_str(&env, "SN-42"),
+    );
+    assert_eq!(res, Err(Ok(Error::InvalidIdentifier)));
+
+    // Products with a matching ID but no SGTIN scheme are not found
+    client.register_product(&owner, &batch_config(&env, "(01)04006381333931(21)SN-43"));
+    let res = client.try_get_product_by_gtin_serial(
+        &String::from_str(&env, "4006381333931"),
+        &String::from_str(&env, "SN-43"),
+    );
+    assert_eq!(res, Err(Ok(Error::ProductNotFound)));
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// UPDATE & REVISION HISTORY TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_owner_can_update_product() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+
+    let mut update = update_from(&client, &id);
+    update.name = String::from_str(&env, "Organic Coffee Beans (Grade 1)");
+    update.tags.push_back(String::from_str(&env, "organic"));
+
+    let p = client.update_product(&owner, &id, &update);
+    assert_eq!(p.name, String::from_str(&env, "Organic Coffee Beans (Grade 1)"));
+    assert_eq!(p.tags.len(), 1);
+    assert_eq!(client.get_product(&id), p);
+
+    let original = client.get_product_revision(&id, &0);
+    assert_eq!(original.revision, 0);
+    assert_eq!(original.product.name, String::from_str(&env, "Organic Coffee Beans"));
+    assert_eq!(original.replaced_by, owner);
+}
+
+#[test]
+fn test_update_history_accumulates() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+    assert!(client.get_product_history(&id).is_empty());
+
+    let mut update = update_from(&client, &id);
+    update.description = String::from_str(&env, "First correction");
+    client.update_product(&owner, &id, &update);
+
+    update.category = String::from_str(&env, "Specialty Coffee");
+    client.update_product(&owner, &id, &update);
+
+    let history = client.get_product_history(&id);
+    assert_eq!(history.len(), 2);
+    assert_eq!(
+        history.get_unchecked(0).product.description,
+        String::from_str(&env, "Premium single-origin coffee from Ethiopia")
+    );
+    assert_eq!(
+        history.get_unchecked(1).product.description,
+        String::from_str(&env, "First correction")
+    );
+    assert_eq!(history.get_unchecked(1).product.category, String::from_str(&env, "Coffee"));
+}
+
+#[test]
+fn test_non_owner_cannot_update() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+
+    let mut update = update_from(&client, &id);
+    update.name = String::from_str(&env, "Counterfeit");
+
+    let res = client.try_update_product(&attacker, &id, &update);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_update_is_validated() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+
+    let mut update = update_from(&client, &id);
+    update.name = String::from_str(&env, "");
+
+    let res = client.try_update_product(&owner, &id, &update);
+    assert_eq!(res, Err(Ok(Error::InvalidProductName)));
+    assert!(client.get_product_history(&id).is_empty());
+}
+
+#[test]
+fn test_update_without_changes_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+
+    let update = update_from(&client, &id);
+    let res = client.try_update_product(&owner, &id, &update);
+    assert_eq!(res, Err(Ok(Error::NoChangesInUpdate)));
+}
+
+#[test]
+fn test_deactivated_product_cannot_be_updated() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+    client.deactivate_product(&owner, &id, &String::from_str(&env, "Withdrawn"));
+
+    let mut update = update_from(&client, &id);
+    update.name = String::from_str(&env, "Renamed");
+
+    let res = client.try_update_product(&owner, &id, &update);
+    assert_eq!(res, Err(Ok(Error::ProductDeactivated)));
+}
+
+#[test]
+fn test_get_missing_revision_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+
+    let res = client.try_get_product_revision(&id, &0);
+    assert_eq!(res, Err(Ok(Error::RevisionNotFound)));
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// OWNER INDEX TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_products_by_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "P-0"));
+    configs.push_back(batch_config(&env, "P-1"));
+    client.register_products_batch(&owner, &configs);
+    client.register_product(&other, &batch_config(&env, "P-OTHER"));
+
+    assert_eq!(client.get_owner_product_count(&owner), 3);
+    assert_eq!(client.get_owner_product_count(&other), 1);
+
+    let page = client.get_products_by_owner(&owner, &0, &2);
+    assert_eq!(page.total_count, 3);
+    assert!(page.has_more);
+    assert_eq!(page.products.get_unchecked(0).id, id);
+    assert_eq!(page.products.get_unchecked(1).id, String::from_str(&env, "P-0"));
+
+    let page = client.get_products_by_owner(&owner, &2, &2);
+    assert!(!page.has_more);
+    assert_eq!(page.products.len(), 1);
+    assert_eq!(page.products.get_unchecked(0).id, String::from_str(&env, "P-1"));
+}
+
+#[test]
+fn test_products_by_owner_unknown_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let nobody = Address::generate(&env);
+    let page = client.get_products_by_owner(&nobody, &0, &10);
+    assert_eq!(page.total_count, 0);
+    assert!(page.products.is_empty());
+    assert!(!page.has_more);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// COMPOSITION TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_assemble_product_links_and_consumes_components() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "BIKE"));
+    configs.push_back(batch_config(&env, "FRAME"));
+    configs.push_back(batch_config(&env, "WHEEL"));
+    client.register_products_batch(&owner, &configs);
+
+    let bike = String::from_str(&env, "BIKE");
+    let frame = String::from_str(&env, "FRAME");
+    let wheel = String::from_str(&env, "WHEEL");
+
+    let mut components = Vec::new(&env);
+    components.push_back(frame.clone());
+    components.push_back(wheel.clone());
+    client.assemble_product(&owner, &bike, &components);
+
+    assert_eq!(client.get_components(&bike), components);
+    assert_eq!(client.get_parent(&frame), Some(bike.clone()));
+    assert_eq!(client.get_parent(&bike), None);
+
+    let consumed = client.get_product(&wheel);
+    assert!(!consumed.active);
+    assert_eq!(
+        consumed.deactivation_info.get_unchecked(0).reason,
+        String::from_str(&env, "consumed")
+    );
+
+    let stats = client.get_stats();
+    assert_eq!(stats.total_products, 3);
+    assert_eq!(stats.active_products, 1);
+}
+
+#[test]
+fn test_provenance_includes_nested_components() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "PALLET"));
+    configs.push_back(batch_config(&env, "CASE"));
+    configs.push_back(batch_config(&env, "UNIT-1"));
+    configs.push_back(batch_config(&env, "UNIT-2"));
+    client.register_products_batch(&owner, &configs);
+
+    let mut units = Vec::new(&env);
+    units.push_back(String::from_str(&env, "UNIT-1"));
+    units.push_back(String::from_str(&env, "UNIT-2"));
+    client.assemble_product(&owner, &String::from_str(&env, "CASE"), &units);
+
+    let mut cases = Vec::new(&env);
+    cases.push_back(String::from_str(&env, "CASE"));
+    client.assemble_product(&owner, &String::from_str(&env, "PALLET"), &cases);
+
+    let provenance = client.get_provenance(&String::from_str(&env, "PALLET"));
+    assert_eq!(provenance.len(), 4);
+    assert_eq!(provenance.get_unchecked(0), String::from_str(&env, "PALLET"));
+    assert_eq!(provenance.get_unchecked(1), String::from_str(&env, "CASE"));
+    assert!(provenance.contains(String::from_str(&env, "UNIT-2")));
+}
+
+#[test]
+fn test_assemble_requires_component_authorization() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, auth_client) = setup_with_auth(&env);
+
+    let assembler = Address::generate(&env);
+    let supplier = Address::generate(&env);
+    let parent = String::from_str(&env, "ENGINE");
+    client.register_product(&assembler, &batch_config(&env, "ENGINE"));
+    client.register_product(&supplier, &batch_config(&env, "PISTON"));
+
+    let mut components = Vec::new(&env);
+    components.push_back(String::from_str(&env, "PISTON"));
+
+    let res = client.try_assemble_product(&assembler, &parent, &components);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+    auth_client.add_authorized_actor(&supplier, &String::from_str(&env, "PISTON"), &assembler);
+    client.assemble_product(&assembler, &parent, &components);
+    assert_eq!(client.get_components(&parent).len(), 1);
+}
+
+#[test]
+fn test_consumed_component_cannot_be_reused_or_reactivated() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "A"));
+    configs.push_back(batch_config(&env, "B"));
+    configs.push_back(batch_config(&env, "PART"));
+    client.register_products_batch(&owner, &configs);
+
+    let part = String::from_str(&env, "PART");
+    let mut components = Vec::new(&env);
+    components.push_back(part.clone());
+    client.assemble_product(&owner, &String::from_str(&env, "A"), &components);
+
+    let res = client.try_assemble_product(&owner, &String::from_str(&env, "B"), &components);
+    assert_eq!(res, Err(Ok(Error::ProductConsumed)));
+
+    let res = client.try_reactivate_product(&owner, &part, &String::from_str(&env, ""));
+    assert_eq!(res, Err(Ok(Error::ProductConsumed)));
+}
+
+#[test]
+fn test_assemble_rejects_invalid_component_lists() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "A"));
+    configs.push_back(batch_config(&env, "PART"));
+    client.register_products_batch(&owner, &configs);
+
+    let parent = String::from_str(&env, "A");
+    let part = String::from_str(&env, "PART");
+
+    let res = client.try_assemble_product(&owner, &parent, &Vec::new(&env));
+    assert_eq!(res, Err(Ok(Error::EmptyBatch)));
+
+    let mut components = Vec::new(&env);
+    components.push_back(parent.clone());
+    let res = client.try_assemble_product(&owner, &parent, &components);
+    assert_eq!(res, Err(Ok(Error::InvalidComponent)));
+
+    let mut components = Vec::new(&env);
+    components.push_back(part.clone());
+    components.push_back(part.clone());
+    let res = client.try_assemble_product(&owner, &parent, &components);
+    assert_eq!(res, Err(Ok(Error::DuplicateProductIdInBatch)));
+    assert!(client.get_product(&part).active);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// SPLIT & MERGE TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_split_product_creates_derived_lots() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let container = String::from_str(&env, "CONTAINER");
+    client.register_product(&owner, &batch_config(&env, "CONTAINER"));
+
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "RETAIL-1"));
+    configs.push_back(batch_config(&env, "RETAIL-2"));
+    let lots = client.split_product(&owner, &container, &configs);
+    assert_eq!(lots.len(), 2);
+
+    let retail = String::from_str(&env, "RETAIL-1");
+    assert_eq!(client.get_product(&retail).owner, owner);
+    assert_eq!(client.get_sources(&retail), Vec::from_array(&env, [container.clone()]));
+    assert_eq!(client.get_derivatives(&container).len(), 2);
+
+    let source = client.get_product(&container);
+    assert!(!source.active);
+    assert_eq!(
+        source.deactivation_info.get_unchecked(0).reason,
+        String::from_str(&env, "split")
+    );
+
+    let stats = client.get_stats();
+    assert_eq!(stats.total_products, 3);
+    assert_eq!(stats.active_products, 2);
+
+    let res = client.try_reactivate_product(&owner, &container, &String::from_str(&env, ""));
+    assert_eq!(res, Err(Ok(Error::ProductConsumed)));
+}
+
+#[test]
+fn test_split_is_atomic() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let container = String::from_str(&env, "CONTAINER");
+    client.register_product(&owner, &batch_config(&env, "CONTAINER"));
+
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "RETAIL-1"));
+    configs.push_back(batch_config(&env, "CONTAINER"));
+    let res = client.try_split_product(&owner, &container, &configs);
+    assert_eq!(res, Err(Ok(Error::ProductAlreadyExists)));
+
+    assert!(client.get_product(&container).active);
+    assert!(client.get_derivatives(&container).is_empty());
+    assert_eq!(client.get_stats().total_products, 1);
+}
+
+#[test]
+fn test_non_owner_cannot_split() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let container = String::from_str(&env, "CONTAINER");
+    client.register_product(&owner, &batch_config(&env, "CONTAINER"));
+
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "RETAIL-1"));
+    let res = client.try_split_product(&attacker, &container, &configs);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_authorized_actor_cannot_split_or_merge() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, auth_client) = setup_with_auth(&env);
+
+    let owner = Address::generate(&env);
+    let carrier = Address::generate(&env);
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "FARM-A"));
+    configs.push_back(batch_config(&env, "FARM-B"));
+    client.register_products_batch(&owner, &configs);
+
+    let farm_a = String::from_str(&env, "FARM-A");
+    let farm_b = String::from_str(&env, "FARM-B");
+    auth_client.add_authorized_actor(&owner, &farm_a, &carrier);
+    auth_client.add_authorized_actor(&owner, &farm_b, &carrier);
+
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "RETAIL-1"));
+    let res = client.try_split_product(&carrier, &farm_a, &configs);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+    let sources = Vec::from_array(&env, [farm_a.clone(), farm_b.clone()]);
+    let res = client.try_merge_products(&carrier, &sources, &batch_config(&env, "ROAST-1"));
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+    assert!(client.get_product(&farm_a).active);
+    assert!(client.get_product(&farm_b).active);
+    assert_eq!(client.get_owner_product_count(&carrier), 0);
+}
+
+#[test]
+fn test_merge_products_records_lineage() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let roaster = Address::generate(&env);
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "FARM-A"));
+    configs.push_back(batch_config(&env, "FARM-B"));
+    client.register_products_batch(&roaster, &configs);
+
+    let farm_a = String::from_str(&env, "FARM-A");
+    let farm_b = String::from_str(&env, "FARM-B");
+    let sources = Vec::from_array(&env, [farm_a.clone(), farm_b.clone()]);
+
+    let roast = client.merge_products(&roaster, &sources, &batch_config(&env, "ROAST-1"));
+    assert_eq!(roast.owner, roaster);
+    assert!(roast.active);
+
+    assert_eq!(client.get_sources(&roast.id), sources);
+    assert_eq!(client.get_derivatives(&farm_a), Vec::from_array(&env, [roast.id.clone()]));
+    assert!(!client.get_product(&farm_b).active);
+
+    let provenance = client.get_provenance(&roast.id);
+    assert_eq!(provenance.len(), 3);
+    assert!(provenance.contains(farm_a));
+    assert!(provenance.contains(farm_b));
+
+    let stats = client.get_stats();
+    assert_eq!(stats.total_products, 3);
+    assert_eq!(stats.active_products, 1);
+}
+
+#[test]
+fn test_merge_rejects_invalid_sources() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "FARM-A"));
+    configs.push_back(batch_config(&env, "FARM-B"));
+    client.register_products_batch(&owner, &configs);
+
+    let farm_a = String::from_str(&env, "FARM-A");
+    let farm_b = String::from_str(&env, "FARM-B");
+
+    let res = client.try_merge_products(
+        &owner,
+        &Vec::from_array(&env, [farm_a.clone()]),
+        &batch_config(&env, "ROAST-1"),
+    );
+    assert_eq!(res, Err(Ok(Error::MergeRequiresMultipleSources)));
+
+    let res = client.try_merge_products(
+        &owner,
+        &Vec::from_array(&env, [farm_a.clone(), farm_a.clone()]),
+        &batch_config(&env, "ROAST-1"),
+    );
+    assert_eq!(res, Err(Ok(Error::DuplicateProductIdInBatch)));
+
+    client.deactivate_product(&owner, &farm_b, &String::from_str(&env, "Spoiled"));
+    let res = client.try_merge_products(
+        &owner,
+        &Vec::from_array(&env, [farm_a.clone(), farm_b]),
+        &batch_config(&env, "ROAST-1"),
+    );
+    assert_eq!(res, Err(Ok(Error::ProductDeactivated)));
+    assert!(client.get_product(&farm_a).active);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// STATUS TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_status_follows_allowed_transitions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+    assert_eq!(client.get_product(&id).status, ProductStatus::Registered);
+    assert_eq!(client.get_status_count(&ProductStatus::Registered), 1);
+
+    client.set_status(&owner, &id, &ProductStatus::InStorage);
+    client.set_status(&owner, &id, &ProductStatus::Sold);
+    assert_eq!(client.get_product(&id).status, ProductStatus::Sold);
+    assert_eq!(client.get_status_count(&ProductStatus::Registered), 0);
+    assert_eq!(client.get_status_count(&ProductStatus::Sold), 1);
+
+    let res = client.try_set_status(&owner, &id, &ProductStatus::InTransit);
+    assert_eq!(res, Err(Ok(Error::InvalidStatusTransition)));
+
+    client.set_status(&owner, &id, &ProductStatus::Destroyed);
+    let res = client.try_set_status(&owner, &id, &ProductStatus::InStorage);
+    assert_eq!(res, Err(Ok(Error::InvalidStatusTransition)));
+}
+
+#[test]
+fn test_set_status_requires_owner_or_authorized_actor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, auth_client) = setup_with_auth(&env);
+
+    let owner = Address::generate(&env);
+    let carrier = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+
+    let res = client.try_set_status(&carrier, &id, &ProductStatus::InTransit);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+    auth_client.add_authorized_actor(&owner, &id, &carrier);
+    client.set_status(&carrier, &id, &ProductStatus::InTransit);
+    assert_eq!(client.get_product(&id).status, ProductStatus::InTransit);
+}
+
+#[test]
+fn test_admin_configures_status_transitions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+
+    let res = client.try_set_status_transition(&admin, &ProductStatus::Registered, &ProductStatus::Sold, &true);
+    assert_eq!(res, Err(Ok(Error::NotInitialized)));
+
+    client.configure_admin(&admin);
+    let res = client.try_set_status_transition(&owner, &ProductStatus::Registered, &ProductStatus::Sold, &true);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+    assert!(!client.is_transition_allowed(&ProductStatus::Registered, &ProductStatus::Sold));
+    client.set_status_transition(&admin, &ProductStatus::Registered, &ProductStatus::Sold, &true);
+    assert!(client.is_transition_allowed(&ProductStatus::Registered, &ProductStatus::Sold));
+
+    client.set_status_transition(&admin, &ProductStatus::Registered, &ProductStatus::InStorage, &false);
+    let res = client.try_set_status(&owner, &id, &ProductStatus::InStorage);
+    assert_eq!(res, Err(Ok(Error::InvalidStatusTransition)));
+
+    client.set_status(&owner, &id, &ProductStatus::Sold);
 
-    client
+    // Recalled can only be reached through a recall
+    client.set_status_transition(&admin, &ProductStatus::Sold, &ProductStatus::Recalled, &true);
+    assert!(!client.is_transition_allowed(&ProductStatus::Sold, &ProductStatus::Recalled));
 }
 
-fn register_test_product(
-    env: &Env,
-    client: &ProductRegistryContractClient,
-    owner: &Address,
-) -> String {
-    let id = String::from_str(env, "COFFEE-ETH-001");
-    let config = ProductConfig {
-        id: id.clone(),
-        name: String::from_str(env, "Organic Coffee Beans"),
-        description: String::from_str(env, "Premium single-origin coffee from Ethiopia"),
-        origin_location: String::from_str(env, "Yirgacheffe, Ethiopia"),
-        category: String::from_str(env, "Coffee"),
-        tags: Vec::new(env),
-        certifications: Vec::new(env),
-        media_hashes: Vec::new(env),
-        custom: Map::new(env),
-    };
+#[test]
+fn test_admin_maps_event_types_to_statuses() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
 
-    client.register_product(owner, &config);
-    id
+    let admin = Address::generate(&env);
+    client.configure_admin(&admin);
+
+    let shipped = Symbol::new(&env, "shipped");
+    let dispatched = Symbol::new(&env, "dispatched");
+    assert_eq!(client.get_event_status(&shipped), Some(ProductStatus::InTransit));
+    assert_eq!(client.get_event_status(&dispatched), None);
+
+    client.set_event_status(&admin, &dispatched, &Some(ProductStatus::InTransit));
+    client.set_event_status(&admin, &shipped, &None);
+    assert_eq!(client.get_event_status(&dispatched), Some(ProductStatus::InTransit));
+    assert_eq!(client.get_event_status(&shipped), None);
+}
+
+#[test]
+fn test_recall_moves_products_to_recalled_status() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+    client.set_status(&owner, &id, &ProductStatus::InStorage);
+
+    client.recall_product(
+        &owner,
+        &id,
+        &RecallSeverity::High,
+        &String::from_str(&env, "Foreign matter"),
+        &BytesN::from_array(&env, &[0; 32]),
+    );
+    assert_eq!(client.get_product(&id).status, ProductStatus::Recalled);
+    assert_eq!(client.get_status_count(&ProductStatus::InStorage), 0);
+    assert_eq!(client.get_status_count(&ProductStatus::Recalled), 1);
+
+    // No override moves a recalled product anywhere but Destroyed
+    let admin = Address::generate(&env);
+    client.configure_admin(&admin);
+    client.set_status_transition(&admin, &ProductStatus::Recalled, &ProductStatus::Sold, &true);
+    assert!(!client.is_transition_allowed(&ProductStatus::Recalled, &ProductStatus::Sold));
+    let res = client.try_set_status(&owner, &id, &ProductStatus::Sold);
+    assert_eq!(res, Err(Ok(Error::InvalidStatusTransition)));
+    assert_eq!(client.get_product(&id).status, ProductStatus::Recalled);
+
+    client.set_status(&owner, &id, &ProductStatus::Destroyed);
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
-// REGISTRATION TESTS
+// CERTIFICATION TESTS
 // ═══════════════════════════════════════════════════════════════════════════════
 
 #[test]
-fn test_register_and_get_product() {
+fn test_issue_certification() {
     let env = Env::default();
     env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
     let client = setup(&env);
 
     let owner = Address::generate(&env);
+    let (certifier, _admin) = setup_certifier(&env, &client, &["organic"]);
     let id = register_test_product(&env, &client, &owner);
 
-    let p = client.get_product(&id);
-    assert_eq!(p.id, id);
-    assert_eq!(p.owner, owner);
-    assert!(p.active, "new products must be active");
-    assert!(p.deactivation_info.is_empty(), "no deactivation info on new product");
+    let organic = Symbol::new(&env, "organic");
+    let document = BytesN::from_array(&env, &[9; 32]);
+    let cert_id = client.issue_certification(&certifier, &id, &organic, &document, &5_000);
+    assert_eq!(cert_id, 0);
+
+    let certs = client.get_certifications(&id);
+    assert_eq!(certs.len(), 1);
+    let cert = certs.get_unchecked(0);
+    assert_eq!(cert.standard, organic);
+    assert_eq!(cert.issuer, certifier);
+    assert_eq!(cert.document_hash, document);
+    assert_eq!(cert.issued_at, 1_000);
+    assert_eq!(cert.expires_at, 5_000);
+    assert!(!cert.revoked);
+
+    let res = client.try_issue_certification(&certifier, &id, &organic, &document, &1_000);
+    assert_eq!(res, Err(Ok(Error::InvalidCertificationExpiry)));
+
+    let res = client.try_issue_certification(
+        &certifier,
+        &String::from_str(&env, "MISSING"),
+        &organic,
+        &document,
+        &5_000,
+    );
+    assert_eq!(res, Err(Ok(Error::ProductNotFound)));
 }
 
 #[test]
-fn test_register_increments_stats() {
+fn test_valid_certifications_exclude_expired_and_revoked() {
     let env = Env::default();
     env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
     let client = setup(&env);
 
     let owner = Address::generate(&env);
+    let (certifier, _admin) = setup_certifier(&env, &client, &["organic", "fairtrade", "iso22000"]);
+    let id = register_test_product(&env, &client, &owner);
+    let document = BytesN::from_array(&env, &[0; 32]);
 
-    let stats_before = client.get_stats();
-    assert_eq!(stats_before.total_products, 0);
-    assert_eq!(stats_before.active_products, 0);
+    client.issue_certification(&certifier, &id, &Symbol::new(&env, "organic"), &document, &2_000);
+    client.issue_certification(&certifier, &id, &Symbol::new(&env, "fairtrade"), &document, &9_000);
+    client.issue_certification(&certifier, &id, &Symbol::new(&env, "iso22000"), &document, &9_000);
+    assert_eq!(client.get_valid_certifications(&id).len(), 3);
 
-    register_test_product(&env, &client, &owner);
+    client.revoke_certification(&certifier, &id, &2);
+    env.ledger().with_mut(|l| l.timestamp = 2_000);
 
-    let stats_after = client.get_stats();
-    assert_eq!(stats_after.total_products, 1);
-    assert_eq!(stats_after.active_products, 1);
+    let valid = client.get_valid_certifications(&id);
+    assert_eq!(valid.len(), 1);
+    assert_eq!(valid.get_unchecked(0).standard, Symbol::new(&env, "fairtrade"));
+    assert_eq!(client.get_certifications(&id).len(), 3);
 }
 
 #[test]
-fn test_duplicate_product_rejected() {
+fn test_revoke_certification_authorization() {
     let env = Env::default();
     env.mock_all_auths();
     let client = setup(&env);
 
     let owner = Address::generate(&env);
+    let (certifier, admin) = setup_certifier(&env, &client, &["organic"]);
     let id = register_test_product(&env, &client, &owner);
 
-    let config = ProductConfig {
-        id: id.clone(),
-        name: String::from_str(&env, "Duplicate"),
-        description: String::from_str(&env, ""),
-        origin_location: String::from_str(&env, "Somewhere"),
-        category: String::from_str(&env, "Other"),
-        tags: Vec::new(&env),
-        certifications: Vec::new(&env),
-        media_hashes: Vec::new(&env),
-        custom: Map::new(&env),
-    };
+    let cert_id = client.issue_certification(
+        &certifier,
+        &id,
+        &Symbol::new(&env, "organic"),
+        &BytesN::from_array(&env, &[0; 32]),
+        &100,
+    );
 
-    let res = client.try_register_product(&owner, &config);
-    assert_eq!(res, Err(Ok(Error::ProductAlreadyExists)));
+    let res = client.try_revoke_certification(&owner, &id, &cert_id);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+    let res = client.try_revoke_certification(&certifier, &id, &7);
+    assert_eq!(res, Err(Ok(Error::CertificationNotFound)));
+
+    client.revoke_certification(&admin, &id, &cert_id);
+    assert!(client.get_certifications(&id).get_unchecked(0).revoked);
+
+    let res = client.try_revoke_certification(&certifier, &id, &cert_id);
+    assert_eq!(res, Err(Ok(Error::CertificationRevoked)));
 }
 
 #[test]
-fn test_register_rejects_empty_id() {
+fn test_issue_certification_requires_accreditation() {
     let env = Env::default();
     env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
     let client = setup(&env);
 
     let owner = Address::generate(&env);
-    let config = ProductConfig {
-        id: String::from_str(&env, ""),
-        name: String::from_str(&env, "Name"),
-        description: String::from_str(&env, ""),
-        origin_location: String::from_str(&env, "Origin"),
-        category: String::from_str(&env, "Category"),
-        tags: Vec::new(&env),
-        certifications: Vec::new(&env),
-        media_hashes: Vec::new(&env),
-        custom: Map::new(&env),
-    };
+    let certifier = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+    let organic = Symbol::new(&env, "organic");
+    let document = BytesN::from_array(&env, &[0; 32]);
+
+    // Nothing is accepted before a certifier registry is configured
+    let res = client.try_issue_certification(&certifier, &id, &organic, &document, &5_000);
+    assert_eq!(res, Err(Ok(Error::NotInitialized)));
+
+    let certifier_id = env.register_contract(None, CertifierRegistryContract);
+    let certifiers = CertifierRegistryContractClient::new(&env, &certifier_id);
+    let accreditor = Address::generate(&env);
+    certifiers.certifier_init(&accreditor);
+    client.configure_admin(&accreditor);
+    let res = client.try_configure_certifier_registry(&certifier, &certifier_id);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+    client.configure_certifier_registry(&accreditor, &certifier_id);
 
-    let res = client.try_register_product(&owner, &config);
-    assert_eq!(res, Err(Ok(Error::InvalidProductId)));
+    let res = client.try_issue_certification(&certifier, &id, &organic, &document, &5_000);
+    assert_eq!(res, Err(Ok(Error::CertifierNotAccredited)));
+
+    certifiers.accredit_certifier(&accreditor, &certifier, &organic, &0, &10_000);
+    client.issue_certification(&certifier, &id, &organic, &document, &5_000);
+
+    // Accreditation is per standard
+    let res = client.try_issue_certification(&certifier, &id, &Symbol::new(&env, "fairtrade"), &document, &5_000);
+    assert_eq!(res, Err(Ok(Error::CertifierNotAccredited)));
+
+    certifiers.suspend_certifier(&accreditor, &certifier, &organic);
+    let res = client.try_issue_certification(&certifier, &id, &organic, &document, &5_000);
+    assert_eq!(res, Err(Ok(Error::CertifierNotAccredited)));
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// ALIAS TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
 #[test]
-fn test_register_rejects_empty_origin() {
+fn test_aliases_are_unique_per_namespace() {
     let env = Env::default();
     env.mock_all_auths();
     let client = setup(&env);
+    let owner = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+    let other = client.register_product(&owner, &batch_config(&env, "TEA-001")).id;
+
+    let sku = Symbol::new(&env, "acme_sku");
+    let erp = Symbol::new(&env, "erp");
+    let value = String::from_str(&env, "A-1001");
 
+    client.add_alias(&owner, &id, &sku, &value);
+    assert_eq!(client.get_alias_target(&sku, &value), id);
+
+    // Taken in this namespace, free in another
+    let res = client.try_add_alias(&owner, &other, &sku, &value);
+    assert_eq!(res, Err(Ok(Error::AliasAlreadyExists)));
+    client.add_alias(&owner, &other, &erp, &value);
+    assert_eq!(client.get_alias_target(&erp, &value), other);
+
+    let aliases = client.get_aliases(&id);
+    assert_eq!(aliases.len(), 1);
+    assert_eq!(aliases.get_unchecked(0), ProductAlias { namespace: sku.clone(), value: value.clone() });
+
+    let res = client.try_add_alias(&owner, &id, &erp, &String::from_str(&env, ""));
+    assert_eq!(res, Err(Ok(Error::InvalidInput)));
+}
+
+#[test]
+fn test_remove_alias_frees_value() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
     let owner = Address::generate(&env);
-    let config = ProductConfig {
-        id: String::from_str(&env, "ID-001"),
-        name: String::from_str(&env, "Name"),
-        description: String::from_str(&env, ""),
-        origin_location: String::from_str(&env, ""),
-        category: String::from_str(&env, "Category"),
-        tags: Vec::new(&env),
-        certifications: Vec::new(&env),
-        media_hashes: Vec::new(&env),
-        custom: Map::new(&env),
-    };
+    let id = register_test_product(&env, &client, &owner);
+    let other = client.register_product(&owner, &batch_config(&env, "TEA-001")).id;
 
-    let res = client.try_register_product(&owner, &config);
-    assert_eq!(res, Err(Ok(Error::InvalidOrigin)));
+    let epc = Symbol::new(&env, "rfid_epc");
+    let value = String::from_str(&env, "urn:epc:id:sgtin:0614141.107346.2017");
+    client.add_alias(&owner, &id, &epc, &value);
+
+    // Only the product the alias points to can release it
+    let res = client.try_remove_alias(&owner, &other, &epc, &value);
+    assert_eq!(res, Err(Ok(Error::InvalidInput)));
+
+    client.remove_alias(&owner, &id, &epc, &value);
+    assert_eq!(client.get_aliases(&id).len(), 0);
+    let res = client.try_get_alias_target(&epc, &value);
+    assert_eq!(res, Err(Ok(Error::ProductNotFound)));
+
+    client.add_alias(&owner, &other, &epc, &value);
+    assert_eq!(client.get_alias_target(&epc, &value), other);
+}
+
+#[test]
+fn test_authorized_actor_can_add_alias() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, auth_client) = setup_with_auth(&env);
+    let owner = Address::generate(&env);
+    let partner = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+
+    let sku = Symbol::new(&env, "partner_sku");
+    let value = String::from_str(&env, "P-77");
+
+    let res = client.try_add_alias(&partner, &id, &sku, &value);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+    auth_client.add_authorized_actor(&owner, &id, &partner);
+    client.add_alias(&partner, &id, &sku, &value);
+    assert_eq!(client.get_alias_target(&sku, &value), id);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RECALL TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_recall_product() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let id = String::from_str(&env, "LOT-1");
+    client.register_product(&owner, &batch_config(&env, "LOT-1"));
+    assert!(!client.is_recalled(&id));
+
+    let reason = String::from_str(&env, "Listeria detected");
+    let evidence = BytesN::from_array(&env, &[7; 32]);
+    let recalled = client.recall_product(&owner, &id, &RecallSeverity::Critical, &reason, &evidence);
+    assert_eq!(recalled, Vec::from_array(&env, [id.clone()]));
+
+    assert!(client.is_recalled(&id));
+    let recall = client.get_recall(&id).unwrap();
+    assert_eq!(recall.origin_id, id);
+    assert_eq!(recall.severity, RecallSeverity::Critical);
+    assert_eq!(recall.evidence_hash, evidence);
+    assert_eq!(recall.recalled_by, owner);
+
+    // Recall is distinct from deactivation
+    assert!(client.get_product(&id).active);
+    assert_eq!(client.get_active_recalls().len(), 1);
+
+    let res = client.try_recall_product(&owner, &id, &RecallSeverity::Low, &reason, &evidence);
+    assert_eq!(res, Err(Ok(Error::ProductAlreadyRecalled)));
+}
+
+#[test]
+fn test_recall_propagates_to_derived_products() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let harvest = String::from_str(&env, "HARVEST");
+    client.register_product(&owner, &batch_config(&env, "HARVEST"));
+    client.register_product(&owner, &batch_config(&env, "UNRELATED"));
+
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "LOT-A"));
+    configs.push_back(batch_config(&env, "LOT-B"));
+    client.split_product(&owner, &harvest, &configs);
+
+    let lot_a = String::from_str(&env, "LOT-A");
+    let pallet = String::from_str(&env, "PALLET");
+    client.register_product(&owner, &batch_config(&env, "PALLET"));
+    client.assemble_product(&owner, &pallet, &Vec::from_array(&env, [lot_a.clone()]));
+
+    let recalled = client.recall_product(
+        &owner,
+        &harvest,
+        &RecallSeverity::High,
+        &String::from_str(&env, "Contaminated water"),
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+    assert_eq!(recalled.len(), 4);
+
+    assert!(client.is_recalled(&lot_a));
+    assert!(client.is_recalled(&String::from_str(&env, "LOT-B")));
+    assert!(client.is_recalled(&pallet));
+    assert!(!client.is_recalled(&String::from_str(&env, "UNRELATED")));
+    assert_eq!(client.get_recall(&pallet).unwrap().origin_id, harvest);
+    assert_eq!(client.get_active_recalls().len(), 4);
+}
+
+#[test]
+fn test_recall_authorization() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let id = String::from_str(&env, "LOT-1");
+    client.register_product(&owner, &batch_config(&env, "LOT-1"));
+
+    let reason = String::from_str(&env, "Mislabelled allergen");
+    let evidence = BytesN::from_array(&env, &[0; 32]);
+
+    let res = client.try_recall_product(&stranger, &id, &RecallSeverity::Medium, &reason, &evidence);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+    let res = client.try_recall_product(&owner, &id, &RecallSeverity::Medium, &String::from_str(&env, ""), &evidence);
+    assert_eq!(res, Err(Ok(Error::RecallReasonRequired)));
+
+    client.configure_admin(&admin);
+    client.recall_product(&admin, &id, &RecallSeverity::Medium, &reason, &evidence);
+    assert_eq!(client.get_recall(&id).unwrap().recalled_by, admin);
+}
+
+#[test]
+fn test_recalled_product_cannot_be_used_or_reactivated() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let mut configs = Vec::new(&env);
+    configs.push_back(batch_config(&env, "LOT-1"));
+    configs.push_back(batch_config(&env, "LOT-2"));
+    client.register_products_batch(&owner, &configs);
+
+    let lot_1 = String::from_str(&env, "LOT-1");
+    let lot_2 = String::from_str(&env, "LOT-2");
+    client.recall_product(
+        &owner,
+        &lot_1,
+        &RecallSeverity::Low,
+        &String::from_str(&env, "Packaging defect"),
+        &BytesN::from_array(&env, &[0; 32]),
+    );
+
+    let res = client.try_merge_products(
+        &owner,
+        &Vec::from_array(&env, [lot_1.clone(), lot_2]),
+        &batch_config(&env, "MERGED"),
+    );
+    assert_eq!(res, Err(Ok(Error::ProductRecalled)));
+
+    client.deactivate_product(&owner, &lot_1, &String::from_str(&env, "Quarantined"));
+    let res = client.try_reactivate_product(&owner, &lot_1, &String::from_str(&env, ""));
+    assert_eq!(res, Err(Ok(Error::ProductRecalled)));
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -300,7 +1554,7 @@ fn test_owner_can_reactivate_product() {
     );
     assert!(!client.get_product(&id).active);
 
-    client.reactivate_product(&owner, &id);
+    client.reactivate_product(&owner, &id, &String::from_str(&env, "Suspension lifted"));
 
     let p = client.get_product(&id);
     assert!(p.active, "product should be active after reactivation");
@@ -322,7 +1576,7 @@ fn test_reactivation_updates_active_counter() {
     client.deactivate_product(&owner, &id, &String::from_str(&env, "Suspended"));
     assert_eq!(client.get_stats().active_products, 0);
 
-    client.reactivate_product(&owner, &id);
+    client.reactivate_product(&owner, &id, &String::from_str(&env, ""));
     assert_eq!(client.get_stats().active_products, 1);
 }
 
@@ -338,7 +1592,7 @@ fn test_non_owner_cannot_reactivate() {
 
     client.deactivate_product(&owner, &id, &String::from_str(&env, "Suspended"));
 
-    let res = client.try_reactivate_product(&attacker, &id);
+    let res = client.try_reactivate_product(&attacker, &id, &String::from_str(&env, ""));
     assert_eq!(res, Err(Ok(Error::Unauthorized)));
 }
 
@@ -351,10 +1605,89 @@ fn test_reactivate_already_active_product() {
     let owner = Address::generate(&env);
     let id = register_test_product(&env, &client, &owner);
 
-    let res = client.try_reactivate_product(&owner, &id);
+    let res = client.try_reactivate_product(&owner, &id, &String::from_str(&env, ""));
     assert_eq!(res, Err(Ok(Error::ProductAlreadyActive)));
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// LIFECYCLE HISTORY TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_lifecycle_history_is_append_only() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+    assert_eq!(client.get_lifecycle_history(&id, &0, &10).total_count, 0);
+
+    client.deactivate_product(&owner, &id, &String::from_str(&env, "Quality hold"));
+    client.reactivate_product(&owner, &id, &String::from_str(&env, "Lab results clear"));
+    client.deactivate_product(&owner, &id, &String::from_str(&env, "Seasonal pause"));
+
+    let page = client.get_lifecycle_history(&id, &0, &10);
+    assert_eq!(page.total_count, 3);
+    assert!(!page.has_more);
+
+    let first = page.entries.get_unchecked(0);
+    assert_eq!(first.action, LifecycleAction::Deactivated);
+    assert_eq!(first.reason, String::from_str(&env, "Quality hold"));
+    assert_eq!(first.by, owner);
+
+    let second = page.entries.get_unchecked(1);
+    assert_eq!(second.action, LifecycleAction::Reactivated);
+    assert_eq!(second.reason, String::from_str(&env, "Lab results clear"));
+
+    // The product record only describes the current deactivation
+    assert_eq!(client.get_product(&id).deactivation_info.len(), 1);
+}
+
+#[test]
+fn test_lifecycle_history_pagination() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+    for _ in 0..3 {
+        client.deactivate_product(&owner, &id, &String::from_str(&env, "Hold"));
+        client.reactivate_product(&owner, &id, &String::from_str(&env, "Release"));
+    }
+
+    let page = client.get_lifecycle_history(&id, &4, &10);
+    assert_eq!(page.total_count, 6);
+    assert_eq!(page.entries.len(), 2);
+    assert!(!page.has_more);
+
+    let page = client.get_lifecycle_history(&id, &0, &2);
+    assert_eq!(page.entries.len(), 2);
+    assert!(page.has_more);
+
+    assert!(client.get_lifecycle_history(&id, &0, &0).entries.is_empty());
+
+    let res = client.try_get_lifecycle_history(&String::from_str(&env, "MISSING"), &0, &10);
+    assert_eq!(res, Err(Ok(Error::ProductNotFound)));
+}
+
+#[test]
+fn test_lifecycle_history_records_retirement() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let owner = Address::generate(&env);
+    let container = String::from_str(&env, "CONTAINER");
+    client.register_product(&owner, &batch_config(&env, "CONTAINER"));
+    client.split_product(&owner, &container, &Vec::from_array(&env, [batch_config(&env, "LOT-1")]));
+
+    let page = client.get_lifecycle_history(&container, &0, &10);
+    assert_eq!(page.total_count, 1);
+    assert_eq!(page.entries.get_unchecked(0).reason, String::from_str(&env, "split"));
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // MULTI-PRODUCT STATS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -371,6 +1704,7 @@ fn test_multiple_products_stats_tracking() {
         let id = String::from_str(&env, &["PROD-", suffix].concat());
         let config = ProductConfig {
             id,
+            id_scheme: IdScheme::Custom,
             name: String::from_str(&env, "Product"),
             description: String::from_str(&env, ""),
             origin_location: String::from_str(&env, "Origin"),
@@ -402,9 +1736,45 @@ fn test_multiple_products_stats_tracking() {
     assert_eq!(stats.total_products, 3, "total includes inactive products");
     assert_eq!(stats.active_products, 1, "only 1 active remaining");
 
-    client.reactivate_product(&owner, &String::from_str(&env, "PROD-B"));
+    client.reactivate_product(&owner, &String::from_str(&env, "PROD-B"), &String::from_str(&env, ""));
 
     let stats = client.get_stats();
     assert_eq!(stats.total_products, 3);
     assert_eq!(stats.active_products, 2);
 }
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// STORAGE TTL TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_reads_keep_product_live() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let owner = Address::generate(&env);
+    let id = register_test_product(&env, &client, &owner);
+
+    // Each read extends the product, so regular use outlasts the TTL it
+    // was written with.
+    let step = StorageContract::INSTANCE_BUMP_AMOUNT - StorageContract::DAY_IN_LEDGERS;
+    let mut elapsed = 0;
+    while elapsed <= StorageContract::PERSISTENT_BUMP_AMOUNT {
+        env.ledger().with_mut(|l| l.sequence_number += step);
+        elapsed += step;
+        assert_eq!(client.get_product(&id).id, id);
+    }
+}
+
+#[test]
+fn test_bump_product_requires_existing_product() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+
+    let res = client.try_bump_product(&String::from_str(&env, "MISSING"), &0, &10);
+    assert_eq!(res, Err(Ok(Error::ProductNotFound)));
+
+    let res = client.try_bump_product(&String::from_str(&env, "MISSING"), &0, &0);
+    assert_eq!(res, Err(Ok(Error::InvalidInput)));
+}
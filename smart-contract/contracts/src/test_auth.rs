@@ -5,8 +5,10 @@ use crate::{
     ChainLogisticsContract, ChainLogisticsContractClient, 
     ProductRegistryContract, ProductRegistryContractClient,
     AuthorizationContract, AuthorizationContractClient,
-    Error, ProductConfig,
+    EventTypeRegistryContract, EventTypeRegistryContractClient,
+    Error, IdScheme, ProductConfig,
 };
+use crate::storage;
 
 fn setup(
     env: &Env,
@@ -41,6 +43,7 @@ fn create_test_product(env: &Env, pr_client: &ProductRegistryContractClient, own
         owner,
         &ProductConfig {
             id: id.clone(),
+            id_scheme: IdScheme::Custom,
             name: String::from_str(env, "Test Product"),
             description: String::from_str(env, "Description"),
             origin_location: String::from_str(env, "Origin"),
@@ -115,7 +118,49 @@ fn test_product_lifecycle() {
     assert!(!p.active);
     
     // Reactivate
-    pr_client.reactivate_product(&owner, &id);
+    pr_client.reactivate_product(&owner, &id, &String::from_str(&env, ""));
     let p = pr_client.get_product(&id);
     assert!(p.active);
 }
+
+#[test]
+fn test_legacy_tracking_event_requires_registered_type() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (cl_client, pr_client, _auth_client, admin, _pr_id, _auth_id) = setup(&env);
+    let owner = Address::generate(&env);
+
+    // The legacy entry point reads products from its own storage
+    let id = create_test_product(&env, &pr_client, &owner);
+    let product = pr_client.get_product(&id);
+    env.as_contract(&cl_client.address, || storage::put_product(&env, &product));
+
+    let add_event = |event_type: &Symbol| {
+        cl_client.try_add_tracking_event(
+            &owner,
+            &id,
+            event_type,
+            &String::from_str(&env, "Depot"),
+            &BytesN::from_array(&env, &[0; 32]),
+            &String::from_str(&env, ""),
+            &Map::new(&env),
+        )
+    };
+    let shipped = symbol_short!("shipped");
+    assert_eq!(add_event(&shipped), Err(Ok(Error::NotInitialized)));
+
+    let event_types_id = env.register_contract(None, EventTypeRegistryContract);
+    let event_types_client = EventTypeRegistryContractClient::new(&env, &event_types_id);
+    event_types_client.event_types_init(&admin);
+    event_types_client.register_event_type(&admin, &shipped, &String::from_str(&env, ""), &Vec::new(&env));
+
+    let res = cl_client.try_set_event_type_registry(&owner, &event_types_id);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+    cl_client.set_event_type_registry(&admin, &event_types_id);
+
+    assert_eq!(add_event(&symbol_short!("shiped")), Err(Ok(Error::UnknownEventType)));
+    assert!(add_event(&shipped).is_ok());
+
+    event_types_client.deprecate_event_type(&admin, &shipped);
+    assert_eq!(add_event(&shipped), Err(Ok(Error::UnknownEventType)));
+}
@@ -1,19 +1,366 @@
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Map, String, Symbol, Vec};
+use soroban_sdk::{
+    contract, contractimpl, xdr::ToXdr, Address, Bytes, BytesN, Env, Map, String, Symbol,
+    TryFromVal, Vec,
+};
 
 use crate::error::Error;
+use crate::{event_chain, event_sequence, event_type_registry, telemetry};
+use crate::storage_contract::StorageContract;
 use crate::storage;
-use crate::types::{DataKey, TrackingEvent};
+use crate::types::{
+    DataKey, EffectiveEvent, EventAmendment, EventCorrection, EventDataKey, EventDevice,
+    EventPosition, EventSequence, Excursion, ThresholdPolicy, TrackingEvent, TrackingEventInput,
+};
 use crate::validation_contract::ValidationContract;
-use crate::ChainLogisticsContractClient;
+use crate::{
+    CertifierRegistryContractClient, ChainLogisticsContractClient, DeviceRegistryContractClient,
+    ProductRegistryContractClient,
+};
 
 // ─── Storage helpers for TrackingContract ────────────────────────────────────
 
 fn get_main_contract(env: &Env) -> Option<Address> {
-    env.storage().persistent().get(&DataKey::MainContract)
+    StorageContract::read_instance(env, &DataKey::MainContract)
 }
 
 fn set_main_contract(env: &Env, address: &Address) {
-    env.storage().persistent().set(&DataKey::MainContract, address);
+    StorageContract::write_instance(env, &DataKey::MainContract, address);
+}
+
+fn get_registry_contract(env: &Env) -> Option<Address> {
+    StorageContract::read_instance(env, &DataKey::RegistryContract)
+}
+
+fn set_registry_contract(env: &Env, address: &Address) {
+    StorageContract::write_instance(env, &DataKey::RegistryContract, address);
+}
+
+/// The TrackingContract is administered by the main contract's admin.
+fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
+    let main_contract = get_main_contract(env).ok_or(Error::NotInitialized)?;
+    let admin = ChainLogisticsContractClient::new(env, &main_contract)
+        .get_admin_address()
+        .ok_or(Error::NotInitialized)?;
+    caller.require_auth();
+    if admin != *caller {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn get_certifier_registry(env: &Env) -> Option<Address> {
+    StorageContract::read_instance(env, &DataKey::CertifierRegistry)
+}
+
+fn set_certifier_registry(env: &Env, address: &Address) {
+    StorageContract::write_instance(env, &DataKey::CertifierRegistry, address);
+}
+
+fn get_event_type_registry(env: &Env) -> Option<Address> {
+    StorageContract::read_instance(env, &DataKey::EventTypeRegistry)
+}
+
+fn set_event_type_registry(env: &Env, address: &Address) {
+    StorageContract::write_instance(env, &DataKey::EventTypeRegistry, address);
+}
+
+fn get_device_registry(env: &Env) -> Option<Address> {
+    StorageContract::read_instance(env, &EventDataKey::DeviceRegistry)
+}
+
+fn set_device_registry(env: &Env, address: &Address) {
+    StorageContract::write_instance(env, &EventDataKey::DeviceRegistry, address);
+}
+
+fn get_device_nonce(env: &Env, device: &BytesN<32>) -> u64 {
+    StorageContract::read_persistent(env, &EventDataKey::DeviceNonce(device.clone())).unwrap_or(0)
+}
+
+fn set_device_nonce(env: &Env, device: &BytesN<32>, nonce: u64) {
+    StorageContract::write_persistent(env, &EventDataKey::DeviceNonce(device.clone()), &nonce);
+}
+
+/// Events must use a type registered and not deprecated in the event type
+/// registry, and carry the type's required metadata keys. Events are
+/// refused until a registry is configured.
+fn require_registered_event_type(
+    env: &Env,
+    event_type: &Symbol,
+    metadata: &Map<Symbol, String>,
+) -> Result<(), Error> {
+    let event_types = get_event_type_registry(env).ok_or(Error::NotInitialized)?;
+    event_type_registry::check_registered_event_type(env, &event_types, event_type, metadata)
+}
+
+/// Read the standard named in a `certified` event's metadata as a symbol.
+fn metadata_standard(env: &Env, metadata: &Map<Symbol, String>) -> Result<Symbol, Error> {
+    let standard = metadata
+        .get(Symbol::new(env, "standard"))
+        .ok_or(Error::InvalidInput)?;
+
+    let mut buf = [0u8; 32];
+    let len = standard.len() as usize;
+    if len == 0 || len > buf.len() {
+        return Err(Error::InvalidInput);
+    }
+    standard.copy_into_slice(&mut buf[..len]);
+    let name = core::str::from_utf8(&buf[..len]).map_err(|_| Error::InvalidInput)?;
+    Symbol::try_from_val(env, &name).map_err(|_| Error::InvalidInput)
+}
+
+/// `certified` events must name a `standard` in their metadata that the
+/// actor is accredited for, and are refused until a certifier registry is
+/// configured.
+fn require_accredited_certifier(
+    env: &Env,
+    actor: &Address,
+    event_type: &Symbol,
+    metadata: &Map<Symbol, String>,
+) -> Result<(), Error> {
+    if *event_type != Symbol::new(env, "certified") {
+        return Ok(());
+    }
+    let certifier_registry = get_certifier_registry(env).ok_or(Error::NotInitialized)?;
+
+    let standard = metadata_standard(env, metadata)?;
+    let certifier_client = CertifierRegistryContractClient::new(env, &certifier_registry);
+    if !certifier_client.is_accredited(actor, &standard, &env.ledger().timestamp()) {
+        return Err(Error::CertifierNotAccredited);
+    }
+    Ok(())
+}
+
+/// Recalled products only accept `recall_*` events, and event types
+/// mapped to a status move the product to it. Events are refused until a
+/// registry is configured.
+fn sync_registry(
+    env: &Env,
+    actor: &Address,
+    product_id: &String,
+    event_type: &Symbol,
+) -> Result<(), Error> {
+    let registry = get_registry_contract(env).ok_or(Error::NotInitialized)?;
+    let registry_client = ProductRegistryContractClient::new(env, &registry);
+
+    if registry_client.is_recalled(product_id) {
+        ValidationContract::validate_event_for_recalled(env, event_type)?;
+    }
+
+    let self_address = env.current_contract_address();
+    match registry_client.try_record_event_status(&self_address, actor, product_id, event_type) {
+        Ok(Ok(())) => Ok(()),
+        Err(Ok(e)) => Err(e),
+        _ => Err(Error::InvalidInput),
+    }
+}
+
+/// Event ordering rules for a product's category, read from the main
+/// contract. Needs a registry to look up the category.
+fn product_event_sequence(env: &Env, product_id: &String) -> Result<Option<EventSequence>, Error> {
+    let registry = get_registry_contract(env).ok_or(Error::NotInitialized)?;
+    let registry_client = ProductRegistryContractClient::new(env, &registry);
+    let product = match registry_client.try_get_product(product_id) {
+        Ok(Ok(product)) => product,
+        Err(Ok(e)) => return Err(e),
+        _ => return Err(Error::InvalidInput),
+    };
+
+    let main_contract = get_main_contract(env).ok_or(Error::NotInitialized)?;
+    let main_client = ChainLogisticsContractClient::new(env, &main_contract);
+    Ok(main_client.get_event_sequence(&product.category))
+}
+
+/// Events must follow the event sequence set for the product's category,
+/// if any. Returns the sequence, which `link_event` marks the event under.
+fn require_valid_sequence(
+    env: &Env,
+    product_id: &String,
+    event_type: &Symbol,
+) -> Result<Option<EventSequence>, Error> {
+    let sequence = product_event_sequence(env, product_id)?;
+    if let Some(sequence) = &sequence {
+        event_sequence::check(env, sequence, product_id, event_type)?;
+    }
+    Ok(sequence)
+}
+
+/// The message a device signs for `add_device_event`: the XDR of this
+/// contract's address, the device key, the nonce and the event. Binding the
+/// contract address stops signatures being replayed on another deployment.
+fn device_event_payload(
+    env: &Env,
+    device: &BytesN<32>,
+    nonce: u64,
+    event: &TrackingEventInput,
+) -> Bytes {
+    (env.current_contract_address(), device.clone(), nonce, event.clone()).to_xdr(env)
+}
+
+/// Events may be amended by their original actor or, once a registry is
+/// configured, by the product's owner.
+fn require_can_amend(env: &Env, actor: &Address, event: &TrackingEvent) -> Result<(), Error> {
+    if *actor == event.actor {
+        return Ok(());
+    }
+    let registry = get_registry_contract(env).ok_or(Error::Unauthorized)?;
+    let registry_client = ProductRegistryContractClient::new(env, &registry);
+    match registry_client.try_get_product(&event.product_id) {
+        Ok(Ok(product)) if product.owner == *actor => Ok(()),
+        _ => Err(Error::Unauthorized),
+    }
+}
+
+/// Apply an event's amendments to it, later corrections overriding
+/// earlier ones.
+fn effective_event(env: &Env, mut event: TrackingEvent) -> EffectiveEvent {
+    let amendments = storage::get_event_amendments(env, event.event_id);
+    for amendment in amendments.iter() {
+        let correction = amendment.correction;
+        if correction.location.len() > 0 {
+            event.location = correction.location;
+        }
+        if correction.note.len() > 0 {
+            event.note = correction.note;
+        }
+        if !correction.metadata.is_empty() {
+            event.metadata = correction.metadata;
+        }
+    }
+    EffectiveEvent {
+        event,
+        amended: !amendments.is_empty(),
+        amendment_count: amendments.len(),
+    }
+}
+
+/// An actor-supplied `occurred_at` must lie within the main contract's
+/// timestamp windows.
+fn require_valid_occurred_at(env: &Env, occurred_at: u64) -> Result<(), Error> {
+    if occurred_at == 0 {
+        return Ok(());
+    }
+    let main_contract = get_main_contract(env).ok_or(Error::NotInitialized)?;
+    let main_client = ChainLogisticsContractClient::new(env, &main_contract);
+    let windows = main_client.get_timestamp_windows();
+    ValidationContract::validate_occurred_at(occurred_at, env.ledger().timestamp(), &windows)
+}
+
+/// Checks an event must pass before it is stored: the actor may add
+/// events to the product, and the event's own fields are valid.
+fn validate_event_input(env: &Env, actor: &Address, input: &TrackingEventInput) -> Result<(), Error> {
+    require_event_actor(env, &input.product_id, actor)?;
+    validate_event_fields(env, actor, input)
+}
+
+/// Checks on an event's own fields, which don't depend on the product's
+/// earlier events.
+fn validate_event_fields(env: &Env, actor: &Address, input: &TrackingEventInput) -> Result<(), Error> {
+    ValidationContract::validate_event_location(&input.location)?;
+    ValidationContract::validate_event_position(&input.position)?;
+    ValidationContract::validate_readings(&input.readings)?;
+    require_valid_occurred_at(env, input.occurred_at)?;
+    ValidationContract::validate_event_note(&input.note)?;
+    ValidationContract::validate_metadata(&input.metadata)?;
+    require_registered_event_type(env, &input.event_type, &input.metadata)?;
+    require_accredited_certifier(env, actor, &input.event_type, &input.metadata)
+}
+
+/// The actor must be allowed to add events to the product: it is active
+/// and the actor its owner or an authorized actor.
+fn require_event_actor(env: &Env, product_id: &String, actor: &Address) -> Result<(), Error> {
+    let registry = get_registry_contract(env).ok_or(Error::NotInitialized)?;
+    let registry_client = ProductRegistryContractClient::new(env, &registry);
+    match registry_client.try_check_event_actor(product_id, actor) {
+        Ok(Ok(())) => Ok(()),
+        Err(Ok(e)) => Err(e),
+        _ => Err(Error::InvalidInput),
+    }
+}
+
+/// Store a new event under the next event ID. `input.product_id` is the
+/// product whose chain the record's `prev_event_hash` links into.
+fn store_event(
+    env: &Env,
+    actor: &Address,
+    device: EventDevice,
+    input: TrackingEventInput,
+) -> TrackingEvent {
+    // Generate unique event ID
+    let event_id = storage::next_event_id(env);
+
+    let recorded_at = env.ledger().timestamp();
+    let occurred_at = if input.occurred_at == 0 { recorded_at } else { input.occurred_at };
+    let event = TrackingEvent {
+        event_id,
+        product_id: input.product_id.clone(),
+        actor: actor.clone(),
+        occurred_at,
+        recorded_at,
+        event_type: input.event_type,
+        location: input.location,
+        position: input.position,
+        readings: input.readings,
+        device,
+        data_hash: input.data_hash,
+        prev_event_hash: event_chain::head_hash(env, &input.product_id),
+        note: input.note,
+        metadata: input.metadata,
+    };
+    storage::put_event(env, &event);
+    event
+}
+
+/// Link a stored event into a product's hash chain and indexes, mark it
+/// under the product's event sequence, check its readings against the
+/// product's policy, and emit `tracking_event`.
+fn link_event(
+    env: &Env,
+    product_id: &String,
+    event: &TrackingEvent,
+    sequence: Option<&EventSequence>,
+) {
+    event_chain::advance_head(env, product_id, event);
+
+    // Update product event IDs
+    let mut ids = storage::get_product_event_ids(env, product_id);
+    ids.push_back(event.event_id);
+    storage::put_product_event_ids(env, product_id, &ids);
+
+    // Index by type
+    storage::index_event_by_type(env, product_id, &event.event_type, event.event_id);
+
+    if let Some(sequence) = sequence {
+        event_sequence::record(env, sequence, product_id, event);
+    }
+
+    telemetry::record_readings(env, product_id, event);
+
+    // Emit event
+    env.events().publish(
+        (
+            Symbol::new(env, "tracking_event"),
+            product_id.clone(),
+            event.event_id,
+        ),
+        event.clone(),
+    );
+}
+
+/// Store a validated single-product event and link it into the product's
+/// chain and indexes.
+fn record_event(
+    env: &Env,
+    actor: &Address,
+    device: EventDevice,
+    input: TrackingEventInput,
+) -> Result<u64, Error> {
+    let product_id = input.product_id.clone();
+    let sequence = require_valid_sequence(env, &product_id, &input.event_type)?;
+    sync_registry(env, actor, &product_id, &input.event_type)?;
+
+    let event = store_event(env, actor, device, input);
+    link_event(env, &product_id, &event, sequence.as_ref());
+    Ok(event.event_id)
 }
 
 fn require_not_paused(env: &Env) -> Result<(), Error> {
@@ -41,6 +388,129 @@ impl TrackingContract {
         Ok(())
     }
 
+    /// Configure the ProductRegistryContract consulted for product state.
+    /// Admin only. No events are accepted until it is configured, and the
+    /// registry's admin must name this contract with
+    /// `configure_tracking_contract` for them to go through.
+    ///
+    /// One-time set (or idempotent if set to the same address).
+    pub fn configure_registry(env: Env, admin: Address, registry: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        match get_registry_contract(&env) {
+            None => {
+                set_registry_contract(&env, &registry);
+                Ok(())
+            }
+            Some(existing) if existing == registry => Ok(()),
+            Some(_) => Err(Error::AlreadyInitialized),
+        }
+    }
+
+    /// Configure the CertifierRegistryContract that actors adding
+    /// `certified` events must be accredited in. Admin only.
+    ///
+    /// One-time set (or idempotent if set to the same address).
+    pub fn configure_certifiers(env: Env, admin: Address, certifier_registry: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        match get_certifier_registry(&env) {
+            None => {
+                set_certifier_registry(&env, &certifier_registry);
+                Ok(())
+            }
+            Some(existing) if existing == certifier_registry => Ok(()),
+            Some(_) => Err(Error::AlreadyInitialized),
+        }
+    }
+
+    /// Configure the EventTypeRegistryContract whose types events must use.
+    /// Admin only.
+    ///
+    /// One-time set (or idempotent if set to the same address).
+    pub fn configure_event_types(env: Env, admin: Address, event_types: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        match get_event_type_registry(&env) {
+            None => {
+                set_event_type_registry(&env, &event_types);
+                Ok(())
+            }
+            Some(existing) if existing == event_types => Ok(()),
+            Some(_) => Err(Error::AlreadyInitialized),
+        }
+    }
+
+    /// Get the configured EventTypeRegistryContract, if any.
+    pub fn get_event_type_registry(env: Env) -> Option<Address> {
+        get_event_type_registry(&env)
+    }
+
+    /// Configure the DeviceRegistryContract whose devices may sign events
+    /// through `add_device_event`. Admin only.
+    ///
+    /// One-time set (or idempotent if set to the same address).
+    pub fn configure_devices(env: Env, admin: Address, device_registry: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        match get_device_registry(&env) {
+            None => {
+                set_device_registry(&env, &device_registry);
+                Ok(())
+            }
+            Some(existing) if existing == device_registry => Ok(()),
+            Some(_) => Err(Error::AlreadyInitialized),
+        }
+    }
+
+    /// Get the configured DeviceRegistryContract, if any.
+    pub fn get_device_registry(env: Env) -> Option<Address> {
+        get_device_registry(&env)
+    }
+
+    /// Add a tracking event signed by a registered device rather than
+    /// authorized by an account. The device signs `get_device_event_payload`
+    /// for its next nonce with its ed25519 key; the event is recorded with
+    /// the device's owner as actor and the device's key. Anyone may relay
+    /// it. An invalid signature aborts the call.
+    pub fn add_device_event(
+        env: Env,
+        device: BytesN<32>,
+        nonce: u64,
+        event: TrackingEventInput,
+        signature: BytesN<64>,
+    ) -> Result<u64, Error> {
+        require_not_paused(&env)?;
+        let device_registry = get_device_registry(&env).ok_or(Error::NotInitialized)?;
+        let device_client = DeviceRegistryContractClient::new(&env, &device_registry);
+        // Unknown, revoked and out-of-scope devices alike may not sign
+        let owner = match device_client.try_check_device(&device, &event.product_id, &event.event_type) {
+            Ok(Ok(owner)) => owner,
+            _ => return Err(Error::Unauthorized),
+        };
+
+        if nonce != get_device_nonce(&env, &device) {
+            return Err(Error::InvalidNonce);
+        }
+        let payload = device_event_payload(&env, &device, nonce, &event);
+        env.crypto().ed25519_verify(&device, &payload, &signature);
+        set_device_nonce(&env, &device, nonce + 1);
+
+        validate_event_input(&env, &owner, &event)?;
+        record_event(&env, &owner, EventDevice::Key(device), event)
+    }
+
+    /// Get the nonce a device must sign its next event with.
+    pub fn get_device_nonce(env: Env, device: BytesN<32>) -> u64 {
+        get_device_nonce(&env, &device)
+    }
+
+    /// Get the message a device signs to submit `event` with `nonce`.
+    pub fn get_device_event_payload(
+        env: Env,
+        device: BytesN<32>,
+        nonce: u64,
+        event: TrackingEventInput,
+    ) -> Bytes {
+        device_event_payload(&env, &device, nonce, &event)
+    }
+
     /// Add a new tracking event to a product.
     /// Requires authentication from the actor.
     /// Validates metadata and emits tracking event.
@@ -56,49 +526,61 @@ impl TrackingContract {
     ) -> Result<u64, Error> {
         require_not_paused(&env)?;
         actor.require_auth();
-
-        ValidationContract::validate_event_location(&location)?;
-        ValidationContract::validate_event_note(&note)?;
-        ValidationContract::validate_metadata(&metadata)?;
-
-        // Generate unique event ID
-        let event_id = storage::next_event_id(&env);
-
-        // Create event
-        let event = TrackingEvent {
-            event_id,
-            product_id: product_id.clone(),
-            actor,
-            timestamp: env.ledger().timestamp(),
-            event_type: event_type.clone(),
+        let input = TrackingEventInput {
+            product_id,
+            event_type,
+            occurred_at: 0,
             location,
+            position: EventPosition::None,
+            readings: Vec::new(&env),
             data_hash,
             note,
             metadata,
         };
+        validate_event_input(&env, &actor, &input)?;
+        record_event(&env, &actor, EventDevice::None, input)
+    }
 
-        // Store event
-        storage::put_event(&env, &event);
-
-        // Update product event IDs
-        let mut ids = storage::get_product_event_ids(&env, &product_id);
-        ids.push_back(event_id);
-        storage::put_product_event_ids(&env, &product_id, &ids);
-
-        // Index by type
-        storage::index_event_by_type(&env, &product_id, &event_type, event_id);
+    /// Add a tracking event given as a `TrackingEventInput`, which can carry
+    /// fields `add_tracking_event` doesn't take, such as a position.
+    pub fn submit_tracking_event(
+        env: Env,
+        actor: Address,
+        event: TrackingEventInput,
+    ) -> Result<u64, Error> {
+        require_not_paused(&env)?;
+        actor.require_auth();
+        validate_event_input(&env, &actor, &event)?;
+        record_event(&env, &actor, EventDevice::None, event)
+    }
 
-        // Emit event
-        env.events().publish(
-            (
-                Symbol::new(&env, "tracking_event"),
-                product_id.clone(),
-                event_id,
-            ),
-            event.clone(),
-        );
+    /// Add several tracking events in one call, in order. Every input is
+    /// validated, with the actor's authorization checked once per product,
+    /// before anything is stored; if any event is rejected, none are added.
+    pub fn add_tracking_events_batch(
+        env: Env,
+        actor: Address,
+        events: Vec<TrackingEventInput>,
+    ) -> Result<Vec<u64>, Error> {
+        require_not_paused(&env)?;
+        actor.require_auth();
+        ValidationContract::validate_batch_size(events.len())?;
+
+        let mut checked: Map<String, bool> = Map::new(&env);
+        for input in events.iter() {
+            if checked.contains_key(input.product_id.clone()) {
+                validate_event_fields(&env, &actor, &input)?;
+            } else {
+                validate_event_input(&env, &actor, &input)?;
+                checked.set(input.product_id.clone(), true);
+            }
+        }
 
-        Ok(event_id)
+        let mut event_ids = Vec::new(&env);
+        for input in events.iter() {
+            event_ids.push_back(record_event(&env, &actor, EventDevice::None, input)?);
+        }
+        Ok(event_ids)
     }
 
     /// Get a single event by its ID.
@@ -121,15 +603,234 @@ impl TrackingContract {
     pub fn get_event_count_by_type(env: Env, product_id: String, event_type: Symbol) -> u64 {
         storage::get_event_count_by_type(&env, &product_id, &event_type)
     }
+
+    /// Add one event shared by several products, such as the cartons on a
+    /// pallet. A single record is stored and linked into each product's
+    /// chain and indexes. Needs a registry: the actor must be allowed to
+    /// add events to every product.
+    pub fn add_aggregate_event(
+        env: Env,
+        actor: Address,
+        product_ids: Vec<String>,
+        event_type: Symbol,
+        location: String,
+        data_hash: BytesN<32>,
+        note: String,
+        metadata: Map<Symbol, String>,
+    ) -> Result<u64, Error> {
+        require_not_paused(&env)?;
+        actor.require_auth();
+        ValidationContract::validate_aggregate_size(product_ids.len())?;
+
+        let input = TrackingEventInput {
+            product_id: product_ids.get_unchecked(0),
+            event_type: event_type.clone(),
+            occurred_at: 0,
+            location,
+            position: EventPosition::None,
+            readings: Vec::new(&env),
+            data_hash,
+            note,
+            metadata,
+        };
+        validate_event_fields(&env, &actor, &input)?;
+
+        let mut seen: Map<String, bool> = Map::new(&env);
+        let mut sequences: Map<String, EventSequence> = Map::new(&env);
+        for product_id in product_ids.iter() {
+            if seen.contains_key(product_id.clone()) {
+                return Err(Error::DuplicateProductIdInBatch);
+            }
+            require_event_actor(&env, &product_id, &actor)?;
+            if let Some(sequence) = require_valid_sequence(&env, &product_id, &event_type)? {
+                sequences.set(product_id.clone(), sequence);
+            }
+            sync_registry(&env, &actor, &product_id, &event_type)?;
+            seen.set(product_id, true);
+        }
+
+        let event = store_event(&env, &actor, EventDevice::None, input);
+        for product_id in product_ids.iter() {
+            if product_id != event.product_id {
+                let link = event_chain::head_hash(&env, &product_id);
+                storage::set_aggregate_link(&env, event.event_id, &product_id, &link);
+            }
+            let sequence = sequences.get(product_id.clone());
+            link_event(&env, &product_id, &event, sequence.as_ref());
+        }
+        storage::set_aggregate_products(&env, event.event_id, &product_ids);
+
+        Ok(event.event_id)
+    }
+
+    /// Set a product's cold-chain threshold policy, replacing any existing
+    /// one. Readings already recorded are not re-checked. Needs a registry;
+    /// only the product's owner may set the policy.
+    pub fn set_threshold_policy(
+        env: Env,
+        owner: Address,
+        product_id: String,
+        policy: ThresholdPolicy,
+    ) -> Result<(), Error> {
+        require_not_paused(&env)?;
+        owner.require_auth();
+        let registry = get_registry_contract(&env).ok_or(Error::NotInitialized)?;
+        let registry_client = ProductRegistryContractClient::new(&env, &registry);
+        let product = match registry_client.try_get_product(&product_id) {
+            Ok(Ok(product)) => product,
+            Err(Ok(e)) => return Err(e),
+            _ => return Err(Error::InvalidInput),
+        };
+        if product.owner != owner {
+            return Err(Error::Unauthorized);
+        }
+        ValidationContract::validate_threshold_policy(&policy)?;
+
+        storage::set_threshold_policy(&env, &product_id, &policy);
+        env.events().publish(
+            (Symbol::new(&env, "threshold_policy_set"), product_id),
+            policy,
+        );
+        Ok(())
+    }
+
+    pub fn get_threshold_policy(env: Env, product_id: String) -> Option<ThresholdPolicy> {
+        storage::get_threshold_policy(&env, &product_id)
+    }
+
+    /// Get a product's temperature excursions, oldest first.
+    pub fn get_excursions(env: Env, product_id: String) -> Vec<Excursion> {
+        storage::get_excursions(&env, &product_id)
+    }
+
+    /// Whether every excursion of the product, including one still open,
+    /// has stayed within its policy's allowed duration. Products without a
+    /// policy are compliant.
+    pub fn is_compliant(env: Env, product_id: String) -> bool {
+        telemetry::is_compliant(&env, &product_id)
+    }
+
+    /// Get the products an aggregate event was recorded for. Empty for
+    /// events recorded for a single product.
+    pub fn get_aggregate_products(env: Env, event_id: u64) -> Vec<String> {
+        storage::get_aggregate_products(&env, event_id)
+    }
+
+    /// Append a correction to an event. The event itself is never changed;
+    /// its corrected form is returned by `get_effective_event`.
+    /// Only the event's actor or the product's owner may amend it.
+    pub fn amend_event(
+        env: Env,
+        actor: Address,
+        event_id: u64,
+        correction: EventCorrection,
+        reason: String,
+    ) -> Result<u32, Error> {
+        require_not_paused(&env)?;
+        actor.require_auth();
+        let event = storage::get_event(&env, event_id).ok_or(Error::EventNotFound)?;
+        require_can_amend(&env, &actor, &event)?;
+        ValidationContract::validate_event_correction(&correction, &reason)?;
+
+        let index = storage::get_event_amendment_count(&env, event_id);
+        if index >= ValidationContract::MAX_AMENDMENTS {
+            return Err(Error::InvalidInput);
+        }
+
+        let amendment = EventAmendment {
+            event_id,
+            index,
+            correction,
+            reason,
+            amended_by: actor,
+            amended_at: env.ledger().timestamp(),
+        };
+        storage::append_event_amendment(&env, &amendment);
+
+        env.events().publish(
+            (
+                Symbol::new(&env, "event_amended"),
+                event.product_id,
+                event_id,
+            ),
+            amendment,
+        );
+
+        Ok(index)
+    }
+
+    /// Get an event's amendments, oldest first.
+    pub fn get_event_amendments(env: Env, event_id: u64) -> Vec<EventAmendment> {
+        storage::get_event_amendments(&env, event_id)
+    }
+
+    /// Get an event with its amendments applied.
+    pub fn get_effective_event(env: Env, event_id: u64) -> Result<EffectiveEvent, Error> {
+        let event = storage::get_event(&env, event_id).ok_or(Error::EventNotFound)?;
+        Ok(effective_event(&env, event))
+    }
+
+    /// Get a page of a product's events with their amendments applied.
+    pub fn get_effective_events(
+        env: Env,
+        product_id: String,
+        offset: u64,
+        limit: u64,
+    ) -> Vec<EffectiveEvent> {
+        let mut events = Vec::new(&env);
+        let event_ids = storage::get_product_event_ids_paginated(&env, &product_id, offset, limit);
+        for event_id in event_ids.iter() {
+            if let Some(event) = storage::get_event(&env, event_id) {
+                events.push_back(effective_event(&env, event));
+            }
+        }
+        events
+    }
+
+    /// Get the event types the product's category sequence allows next.
+    /// `None` if the category has no sequence, in which case any type may
+    /// follow. Event types the sequence doesn't name are always allowed.
+    pub fn get_allowed_next_events(env: Env, product_id: String) -> Result<Option<Vec<Symbol>>, Error> {
+        Ok(product_event_sequence(&env, &product_id)?
+            .map(|sequence| event_sequence::allowed_next(&env, &sequence, &product_id)))
+    }
+
+    /// Get the hash of a product's latest event, which commits to its whole
+    /// event history. `None` if the product has no events.
+    pub fn get_event_head(env: Env, product_id: String) -> Option<BytesN<32>> {
+        storage::get_product_event_head(&env, &product_id)
+    }
+
+    /// Check a product's event hash chain. Returns the ID of the first
+    /// event whose link is broken, or `None` if the chain is intact.
+    pub fn verify_event_chain(env: Env, product_id: String) -> Option<u64> {
+        event_chain::find_broken_link(&env, &product_id)
+    }
+
+    /// Extend the storage TTL of a page of a product's events and this
+    /// contract's instance. Anyone may call this. Returns whether events
+    /// remain past the page; `limit` must be between 1 and 1000.
+    pub fn bump_product_events(env: Env, product_id: String, offset: u64, limit: u64) -> Result<bool, Error> {
+        if limit == 0 || limit > 1000 {
+            return Err(Error::InvalidInput);
+        }
+        StorageContract::extend_instance(&env);
+        Ok(storage::extend_product_events(&env, &product_id, offset, limit))
+    }
 }
 
 #[cfg(test)]
 mod test_tracking {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Address, Env, Map};
+    use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env, Map};
     use crate::{
-        AuthorizationContract, AuthorizationContractClient, ChainLogisticsContract, ChainLogisticsContractClient,
-        ProductConfig, ProductRegistryContract, ProductRegistryContractClient,
+        AuthorizationContract, AuthorizationContractClient, CertifierRegistryContract,
+        CertifierRegistryContractClient, ChainLogisticsContract, ChainLogisticsContractClient,
+        DeviceRegistryContract, DeviceRegistryContractClient,
+        IdScheme, ProductConfig, ProductRegistryContract, ProductRegistryContractClient, ProductStatus,
+        EventCorrection, EventSequence, EventTypeRegistryContract, EventTypeRegistryContractClient,
+        RecallSeverity, SensorReading,
+        SensorUnit, SequenceRule, TimestampWindows, TrackingEventInput,
     };
 
     fn setup_uninitialized(env: &Env) -> (ChainLogisticsContractClient, ProductRegistryContractClient, Address, Address, super::TrackingContractClient) {
@@ -152,12 +853,46 @@ mod test_tracking {
         (cl_client, registry_client, admin, cl_id, tracking_client)
     }
 
-    fn setup_initialized(env: &Env) -> (ChainLogisticsContractClient, ProductRegistryContractClient, Address, Address, super::TrackingContractClient) {
+    fn setup_wired(
+        env: &Env,
+    ) -> (ChainLogisticsContractClient<'_>, ProductRegistryContractClient<'_>, Address, Address, super::TrackingContractClient<'_>) {
         let (cl_client, registry_client, admin, cl_id, tracking_client) = setup_uninitialized(env);
         tracking_client.init(&cl_id);
+        registry_client.configure_admin(&admin);
+        registry_client.configure_tracking_contract(&admin, &tracking_client.address);
+        tracking_client.configure_registry(&admin, &registry_client.address);
+        (cl_client, registry_client, admin, cl_id, tracking_client)
+    }
+
+    fn setup_initialized(env: &Env) -> (ChainLogisticsContractClient, ProductRegistryContractClient, Address, Address, super::TrackingContractClient) {
+        let (cl_client, registry_client, admin, cl_id, tracking_client) = setup_wired(env);
+        let event_types = [
+            "created", "produced", "packed", "shipped", "received", "scanned", "sensed", "inspected",
+            "shelved", "harvested", "sold", "certified", "recall", "recall_in", "recall_returned",
+        ];
+        let (event_types_id, _types_admin) = register_event_types(env, &event_types);
+        tracking_client.configure_event_types(&admin, &event_types_id);
         (cl_client, registry_client, admin, cl_id, tracking_client)
     }
 
+    /// Deploy an event type registry with `event_types` registered, none
+    /// requiring metadata. Returns the registry and its admin.
+    fn register_event_types(env: &Env, event_types: &[&str]) -> (Address, Address) {
+        let event_types_id = env.register_contract(None, EventTypeRegistryContract);
+        let event_types_client = EventTypeRegistryContractClient::new(env, &event_types_id);
+        let types_admin = Address::generate(env);
+        event_types_client.event_types_init(&types_admin);
+        for event_type in event_types {
+            event_types_client.register_event_type(
+                &types_admin,
+                &Symbol::new(env, event_type),
+                &String::from_str(env, ""),
+                &Vec::new(env),
+            );
+        }
+        (event_types_id, types_admin)
+    }
+
     fn register_test_product(
         env: &Env,
         client: &ProductRegistryContractClient,
@@ -169,6 +904,7 @@ mod test_tracking {
             owner,
             &ProductConfig {
                 id: product_id.clone(),
+                id_scheme: IdScheme::Custom,
                 name: String::from_str(env, "Test Product"),
                 description: String::from_str(env, "Description"),
                 origin_location: String::from_str(env, "Origin"),
@@ -182,6 +918,17 @@ mod test_tracking {
         product_id
     }
 
+    fn authorize_actor(
+        env: &Env,
+        registry_client: &ProductRegistryContractClient,
+        owner: &Address,
+        product_id: &String,
+        actor: &Address,
+    ) {
+        let auth_id = env.as_contract(&registry_client.address, || storage::get_auth_contract(env).unwrap());
+        AuthorizationContractClient::new(env, &auth_id).add_authorized_actor(owner, product_id, actor);
+    }
+
     #[test]
     fn test_add_tracking_event() {
         let env = Env::default();
@@ -546,32 +1293,928 @@ mod test_tracking {
     }
 
     #[test]
-    fn test_add_event_before_init_fails() {
+    fn test_registry_wiring_requires_admins() {
         let env = Env::default();
         env.mock_all_auths();
 
-        let tracking_id = env.register_contract(None, super::TrackingContract);
-        let tracking_client = super::TrackingContractClient::new(&env, &tracking_id);
-
+        let (_cl_client, registry_client, admin, cl_id, tracking_client) = setup_uninitialized(&env);
+        tracking_client.init(&cl_id);
+        registry_client.configure_admin(&admin);
         let owner = Address::generate(&env);
-        let product_id = String::from_str(&env, "PROD1");
-        let event_type = Symbol::new(&env, "created");
-        let location = String::from_str(&env, "Warehouse A");
-        let data_hash = BytesN::from_array(&env, &[0; 32]);
-        let metadata = Map::new(&env);
+        let stranger = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &owner, "PROD1");
 
-        // Adding event without initialization should fail because pause enforcement requires
-        // the main contract address to be set via init.
+        // No events until the registry is wired
         let res = tracking_client.try_add_tracking_event(
             &owner,
             &product_id,
-            &event_type,
-            &location,
-            &data_hash,
-            &String::from_str(&env, "Test event"),
-            &metadata,
+            &Symbol::new(&env, "shipped"),
+            &String::from_str(&env, "Port of Rotterdam"),
+            &BytesN::from_array(&env, &[0; 32]),
+            &String::from_str(&env, ""),
+            &Map::new(&env),
         );
-
         assert_eq!(res, Err(Ok(Error::NotInitialized)));
+
+        assert_eq!(
+            tracking_client.try_configure_registry(&stranger, &registry_client.address),
+            Err(Ok(Error::Unauthorized))
+        );
+        assert_eq!(
+            registry_client.try_configure_tracking_contract(&stranger, &tracking_client.address),
+            Err(Ok(Error::Unauthorized))
+        );
+
+        tracking_client.configure_registry(&admin, &registry_client.address);
+        registry_client.configure_tracking_contract(&admin, &tracking_client.address);
+        let (event_types_id, _types_admin) = register_event_types(&env, &["shipped"]);
+        tracking_client.configure_event_types(&admin, &event_types_id);
+        tracking_client.add_tracking_event(
+            &owner,
+            &product_id,
+            &Symbol::new(&env, "shipped"),
+            &String::from_str(&env, "Port of Rotterdam"),
+            &BytesN::from_array(&env, &[0; 32]),
+            &String::from_str(&env, ""),
+            &Map::new(&env),
+        );
+        assert_eq!(registry_client.get_product(&product_id).status, ProductStatus::InTransit);
+    }
+
+    #[test]
+    fn test_recalled_product_accepts_only_recall_events() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_cl_client, registry_client, _admin, _cl_id, tracking_client) = setup_initialized(&env);
+
+        let owner = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &owner, "PROD1");
+        let data_hash = BytesN::from_array(&env, &[0; 32]);
+        registry_client.recall_product(
+            &owner,
+            &product_id,
+            &RecallSeverity::Critical,
+            &String::from_str(&env, "Contamination"),
+            &data_hash,
+        );
+
+        let location = String::from_str(&env, "Store 12");
+        let note = String::from_str(&env, "");
+        let metadata = Map::new(&env);
+
+        let res = tracking_client.try_add_tracking_event(
+            &owner,
+            &product_id,
+            &Symbol::new(&env, "shipped"),
+            &location,
+            &data_hash,
+            &note,
+            &metadata,
+        );
+        assert_eq!(res, Err(Ok(Error::ProductRecalled)));
+
+        let res = tracking_client.try_add_tracking_event(
+            &owner,
+            &product_id,
+            &Symbol::new(&env, "recall"),
+            &location,
+            &data_hash,
+            &note,
+            &metadata,
+        );
+        assert_eq!(res, Err(Ok(Error::ProductRecalled)));
+
+        let event_id = tracking_client.add_tracking_event(
+            &owner,
+            &product_id,
+            &Symbol::new(&env, "recall_returned"),
+            &location,
+            &data_hash,
+            &note,
+            &metadata,
+        );
+        assert_eq!(tracking_client.get_event(&event_id).event_type, Symbol::new(&env, "recall_returned"));
+
+        // Short names are stored inline rather than as objects; both work
+        tracking_client.add_tracking_event(
+            &owner,
+            &product_id,
+            &Symbol::new(&env, "recall_in"),
+            &location,
+            &data_hash,
+            &note,
+            &metadata,
+        );
+        assert_eq!(tracking_client.get_event_count(&product_id), 2);
+    }
+
+    #[test]
+    fn test_tracking_events_drive_product_status() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_cl_client, registry_client, _admin, _cl_id, tracking_client) = setup_initialized(&env);
+
+        let owner = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &owner, "PROD1");
+        let location = String::from_str(&env, "Port of Rotterdam");
+        let data_hash = BytesN::from_array(&env, &[0; 32]);
+        let note = String::from_str(&env, "");
+        let metadata = Map::new(&env);
+
+        // A stranger can't move someone else's product
+        let stranger = Address::generate(&env);
+        let res = tracking_client.try_add_tracking_event(
+            &stranger,
+            &product_id,
+            &Symbol::new(&env, "shipped"),
+            &location,
+            &data_hash,
+            &note,
+            &metadata,
+        );
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+        assert_eq!(registry_client.get_product(&product_id).status, ProductStatus::Registered);
+        assert_eq!(tracking_client.get_event_count(&product_id), 0);
+
+        tracking_client.add_tracking_event(
+            &owner,
+            &product_id,
+            &Symbol::new(&env, "shipped"),
+            &location,
+            &data_hash,
+            &note,
+            &metadata,
+        );
+        assert_eq!(registry_client.get_product(&product_id).status, ProductStatus::InTransit);
+
+        // Unmapped event types leave the status alone
+        tracking_client.add_tracking_event(
+            &owner,
+            &product_id,
+            &Symbol::new(&env, "inspected"),
+            &location,
+            &data_hash,
+            &note,
+            &metadata,
+        );
+        assert_eq!(registry_client.get_product(&product_id).status, ProductStatus::InTransit);
+
+        // Sold straight from transit is not allowed, and the event is rejected
+        let res = tracking_client.try_add_tracking_event(
+            &owner,
+            &product_id,
+            &Symbol::new(&env, "sold"),
+            &location,
+            &data_hash,
+            &note,
+            &metadata,
+        );
+        assert_eq!(res, Err(Ok(Error::InvalidStatusTransition)));
+        assert_eq!(tracking_client.get_event_count(&product_id), 2);
+    }
+
+    #[test]
+    fn test_certified_events_require_accreditation() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_cl_client, registry_client, admin, _cl_id, tracking_client) = setup_initialized(&env);
+        let owner = Address::generate(&env);
+        let inspector = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &owner, "PROD1");
+        authorize_actor(&env, &registry_client, &owner, &product_id, &inspector);
+        let certified = Symbol::new(&env, "certified");
+        let location = String::from_str(&env, "Farm 3");
+        let data_hash = BytesN::from_array(&env, &[0; 32]);
+        let note = String::from_str(&env, "Annual audit");
+
+        let mut metadata = Map::new(&env);
+        metadata.set(Symbol::new(&env, "standard"), String::from_str(&env, "organic"));
+
+        // Refused until a certifier registry is configured
+        let res = tracking_client.try_add_tracking_event(
+            &inspector, &product_id, &certified, &location, &data_hash, &note, &metadata,
+        );
+        assert_eq!(res, Err(Ok(Error::NotInitialized)));
+
+        let certifier_id = env.register_contract(None, CertifierRegistryContract);
+        let certifiers = CertifierRegistryContractClient::new(&env, &certifier_id);
+        let accreditor = Address::generate(&env);
+        certifiers.certifier_init(&accreditor);
+        assert_eq!(
+            tracking_client.try_configure_certifiers(&accreditor, &certifier_id),
+            Err(Ok(Error::Unauthorized))
+        );
+        tracking_client.configure_certifiers(&admin, &certifier_id);
+
+        let res = tracking_client.try_add_tracking_event(
+            &inspector, &product_id, &certified, &location, &data_hash, &note, &metadata,
+        );
+        assert_eq!(res, Err(Ok(Error::CertifierNotAccredited)));
+
+        // The standard must be named
+        let res = tracking_client.try_add_tracking_event(
+            &inspector, &product_id, &certified, &location, &data_hash, &note, &Map::new(&env),
+        );
+        assert_eq!(res, Err(Ok(Error::InvalidInput)));
+
+        certifiers.accredit_certifier(&accreditor, &inspector, &Symbol::new(&env, "organic"), &0, &1_000);
+        tracking_client.add_tracking_event(
+            &inspector, &product_id, &certified, &location, &data_hash, &note, &metadata,
+        );
+        assert_eq!(tracking_client.get_event_count(&product_id), 1);
+    }
+
+    #[test]
+    fn test_add_event_before_init_fails() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let tracking_id = env.register_contract(None, super::TrackingContract);
+        let tracking_client = super::TrackingContractClient::new(&env, &tracking_id);
+
+        let owner = Address::generate(&env);
+        let product_id = String::from_str(&env, "PROD1");
+        let event_type = Symbol::new(&env, "created");
+        let location = String::from_str(&env, "Warehouse A");
+        let data_hash = BytesN::from_array(&env, &[0; 32]);
+        let metadata = Map::new(&env);
+
+        // Adding event without initialization should fail because pause enforcement requires
+        // the main contract address to be set via init.
+        let res = tracking_client.try_add_tracking_event(
+            &owner,
+            &product_id,
+            &event_type,
+            &location,
+            &data_hash,
+            &String::from_str(&env, "Test event"),
+            &metadata,
+        );
+
+        assert_eq!(res, Err(Ok(Error::NotInitialized)));
+    }
+
+    #[test]
+    fn test_bump_product_keeps_events_live() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_cl_client, registry_client, _admin, _cl_id, tracking_client) = setup_initialized(&env);
+
+        let owner = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &owner, "PROD1");
+        let mut event_ids = Vec::new(&env);
+        for _ in 0..3 {
+            event_ids.push_back(tracking_client.add_tracking_event(
+                &owner,
+                &product_id,
+                &Symbol::new(&env, "shipped"),
+                &String::from_str(&env, "Port of Rotterdam"),
+                &BytesN::from_array(&env, &[0; 32]),
+                &String::from_str(&env, ""),
+                &Map::new(&env),
+            ));
+        }
+
+        // Outlive the TTL given on write, bumping well within each
+        // instance TTL window.
+        let step = StorageContract::INSTANCE_BUMP_AMOUNT - StorageContract::DAY_IN_LEDGERS;
+        let mut elapsed = 0;
+        while elapsed <= StorageContract::PERSISTENT_BUMP_AMOUNT {
+            env.ledger().with_mut(|l| l.sequence_number += step);
+            elapsed += step;
+            // Two events per page, so the product takes two calls
+            assert!(registry_client.bump_product(&product_id, &0, &2));
+            assert!(!registry_client.bump_product(&product_id, &2, &2));
+        }
+
+        for event_id in event_ids.iter() {
+            assert_eq!(tracking_client.get_event(&event_id).product_id, product_id);
+        }
+        env.as_contract(&tracking_client.address, || {
+            let shipped = Symbol::new(&env, "shipped");
+            assert_eq!(StorageContract::get_event_ids_by_type(&env, &product_id, &shipped, 0, 10), event_ids);
+        });
+        assert_eq!(tracking_client.get_event_count(&product_id), 3);
+        assert_eq!(registry_client.get_product(&product_id).id, product_id);
+    }
+
+    #[test]
+    fn test_events_form_hash_chain() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_cl_client, registry_client, _admin, _cl_id, tracking_client) = setup_initialized(&env);
+        let owner = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &owner, "PROD1");
+        assert_eq!(tracking_client.get_event_head(&product_id), None);
+        assert_eq!(tracking_client.verify_event_chain(&product_id), None);
+
+        let mut ids = Vec::new(&env);
+        for event_type in ["produced", "shipped", "received"] {
+            ids.push_back(tracking_client.add_tracking_event(
+                &owner,
+                &product_id,
+                &Symbol::new(&env, event_type),
+                &String::from_str(&env, "Warehouse 4"),
+                &BytesN::from_array(&env, &[7; 32]),
+                &String::from_str(&env, ""),
+                &Map::new(&env),
+            ));
+        }
+
+        let first = tracking_client.get_event(&ids.get_unchecked(0));
+        let second = tracking_client.get_event(&ids.get_unchecked(1));
+        let third = tracking_client.get_event(&ids.get_unchecked(2));
+        assert_eq!(first.prev_event_hash, BytesN::from_array(&env, &[0; 32]));
+        assert_eq!(second.prev_event_hash, event_chain::hash_event(&env, &first));
+        assert_eq!(third.prev_event_hash, event_chain::hash_event(&env, &second));
+        assert_eq!(
+            tracking_client.get_event_head(&product_id),
+            Some(event_chain::hash_event(&env, &third))
+        );
+        assert_eq!(tracking_client.verify_event_chain(&product_id), None);
+
+        // Rewriting an event breaks the link from the event after it
+        let mut forged = second.clone();
+        forged.note = String::from_str(&env, "edited");
+        env.as_contract(&tracking_client.address, || storage::put_event(&env, &forged));
+        assert_eq!(tracking_client.verify_event_chain(&product_id), Some(third.event_id));
+
+        // The latest event is covered by the head hash
+        env.as_contract(&tracking_client.address, || storage::put_event(&env, &second));
+        let mut forged = third.clone();
+        forged.location = String::from_str(&env, "Elsewhere");
+        env.as_contract(&tracking_client.address, || storage::put_event(&env, &forged));
+        assert_eq!(tracking_client.verify_event_chain(&product_id), Some(third.event_id));
+    }
+
+    #[test]
+    fn test_event_types_enforced_once_configured() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_cl_client, registry_client, admin, _cl_id, tracking_client) = setup_wired(&env);
+        let owner = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &owner, "PROD1");
+        let add_event = |event_type: &Symbol, metadata: &Map<Symbol, String>| {
+            tracking_client.try_add_tracking_event(
+                &owner,
+                &product_id,
+                event_type,
+                &String::from_str(&env, "Depot"),
+                &BytesN::from_array(&env, &[0; 32]),
+                &String::from_str(&env, ""),
+                metadata,
+            )
+        };
+        let misspelled = Symbol::new(&env, "Shiped");
+        let inspected = Symbol::new(&env, "inspected");
+        let inspector = Symbol::new(&env, "inspector");
+
+        // No type goes until a registry is configured
+        assert_eq!(add_event(&misspelled, &Map::new(&env)), Err(Ok(Error::NotInitialized)));
+
+        let event_types_id = env.register_contract(None, EventTypeRegistryContract);
+        let event_types_client = EventTypeRegistryContractClient::new(&env, &event_types_id);
+        let types_admin = Address::generate(&env);
+        event_types_client.event_types_init(&types_admin);
+        let mut required = Vec::new(&env);
+        required.push_back(inspector.clone());
+        event_types_client.register_event_type(
+            &types_admin,
+            &inspected,
+            &String::from_str(&env, "Quality inspection"),
+            &required,
+        );
+        assert_eq!(
+            tracking_client.try_configure_event_types(&types_admin, &event_types_id),
+            Err(Ok(Error::Unauthorized))
+        );
+        tracking_client.configure_event_types(&admin, &event_types_id);
+        assert_eq!(tracking_client.get_event_type_registry(), Some(event_types_id));
+
+        assert_eq!(add_event(&misspelled, &Map::new(&env)), Err(Ok(Error::UnknownEventType)));
+        assert_eq!(add_event(&inspected, &Map::new(&env)), Err(Ok(Error::InvalidInput)));
+
+        let mut metadata = Map::new(&env);
+        metadata.set(inspector, String::from_str(&env, "QA team 2"));
+        assert!(add_event(&inspected, &metadata).is_ok());
+
+        event_types_client.deprecate_event_type(&types_admin, &inspected);
+        assert_eq!(add_event(&inspected, &metadata), Err(Ok(Error::UnknownEventType)));
+    }
+
+    #[test]
+    fn test_event_sequence_enforced_per_category() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (cl_client, registry_client, admin, _cl_id, tracking_client) = setup_initialized(&env);
+        let owner = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &owner, "PROD1");
+        let add_event = |event_type: &Symbol| {
+            tracking_client.try_add_tracking_event(
+                &owner,
+                &product_id,
+                event_type,
+                &String::from_str(&env, "Depot"),
+                &BytesN::from_array(&env, &[0; 32]),
+                &String::from_str(&env, ""),
+                &Map::new(&env),
+            )
+        };
+        let produced = Symbol::new(&env, "produced");
+        let shipped = Symbol::new(&env, "shipped");
+        let received = Symbol::new(&env, "received");
+        let inspected = Symbol::new(&env, "inspected");
+
+        assert_eq!(tracking_client.get_allowed_next_events(&product_id), None);
+
+        let mut first = Vec::new(&env);
+        first.push_back(produced.clone());
+        let mut rules = Vec::new(&env);
+        let mut after_produced = Vec::new(&env);
+        after_produced.push_back(shipped.clone());
+        rules.push_back(SequenceRule { from: produced.clone(), next: after_produced.clone() });
+        let mut after_shipped = Vec::new(&env);
+        after_shipped.push_back(received.clone());
+        rules.push_back(SequenceRule { from: shipped.clone(), next: after_shipped.clone() });
+        cl_client.set_event_sequence(
+            &admin,
+            &String::from_str(&env, "Category"),
+            &EventSequence { first: first.clone(), rules },
+        );
+
+        assert_eq!(tracking_client.get_allowed_next_events(&product_id), Some(first));
+        assert_eq!(add_event(&received), Err(Ok(Error::InvalidEventSequence)));
+        // Types the sequence doesn't name are unrestricted
+        assert!(add_event(&inspected).is_ok());
+
+        assert!(add_event(&produced).is_ok());
+        assert!(add_event(&inspected).is_ok());
+        assert_eq!(tracking_client.get_allowed_next_events(&product_id), Some(after_produced));
+        assert_eq!(add_event(&produced), Err(Ok(Error::InvalidEventSequence)));
+
+        assert!(add_event(&shipped).is_ok());
+        assert_eq!(tracking_client.get_allowed_next_events(&product_id), Some(after_shipped));
+        assert!(add_event(&received).is_ok());
+        assert_eq!(tracking_client.get_allowed_next_events(&product_id), Some(Vec::new(&env)));
+    }
+
+    #[test]
+    fn test_event_sequence_survives_unsequenced_events() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (cl_client, registry_client, admin, _cl_id, tracking_client) = setup_initialized(&env);
+        let owner = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &owner, "PROD1");
+        // Each call gets a transaction's budget
+        let add_event = |event_type: &str| {
+            env.budget().reset_default();
+            tracking_client.try_add_tracking_event(
+                &owner,
+                &product_id,
+                &Symbol::new(&env, event_type),
+                &String::from_str(&env, "Depot"),
+                &BytesN::from_array(&env, &[0; 32]),
+                &String::from_str(&env, ""),
+                &Map::new(&env),
+            )
+        };
+        let sequence = |after_shipped: &[&str]| {
+            let mut next = Vec::new(&env);
+            for event_type in after_shipped {
+                next.push_back(Symbol::new(&env, event_type));
+            }
+            let mut rules = Vec::new(&env);
+            rules.push_back(SequenceRule {
+                from: Symbol::new(&env, "produced"),
+                next: Vec::from_array(&env, [Symbol::new(&env, "shipped")]),
+            });
+            rules.push_back(SequenceRule { from: Symbol::new(&env, "shipped"), next: next.clone() });
+            cl_client.set_event_sequence(
+                &admin,
+                &String::from_str(&env, "Category"),
+                &EventSequence { first: Vec::from_array(&env, [Symbol::new(&env, "produced")]), rules },
+            );
+            next
+        };
+
+        sequence(&["received"]);
+        assert!(add_event("produced").is_ok());
+        assert!(add_event("shipped").is_ok());
+        // More unsequenced readings than a reconfigured sequence rescans
+        for _ in 0..60 {
+            assert!(add_event("sensed").is_ok());
+        }
+        assert_eq!(add_event("produced"), Err(Ok(Error::InvalidEventSequence)));
+
+        // A new sequence still knows the product was last shipped
+        let after_shipped = sequence(&["received", "inspected"]);
+        env.budget().reset_default();
+        assert_eq!(tracking_client.get_allowed_next_events(&product_id), Some(after_shipped));
+        assert!(add_event("sensed").is_ok());
+        assert!(add_event("inspected").is_ok());
+        assert_eq!(tracking_client.get_allowed_next_events(&product_id), Some(Vec::new(&env)));
+    }
+
+    #[test]
+    fn test_amend_event_appends_corrections() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_cl_client, registry_client, _admin, _cl_id, tracking_client) = setup_initialized(&env);
+        let owner = Address::generate(&env);
+        let carrier = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &owner, "PROD1");
+        authorize_actor(&env, &registry_client, &owner, &product_id, &carrier);
+
+        let event_id = tracking_client.add_tracking_event(
+            &carrier,
+            &product_id,
+            &Symbol::new(&env, "shipped"),
+            &String::from_str(&env, "Warehuose B"),
+            &BytesN::from_array(&env, &[0; 32]),
+            &String::from_str(&env, "Left dock 4"),
+            &Map::new(&env),
+        );
+        let original = tracking_client.get_event(&event_id);
+
+        let fix_location = EventCorrection {
+            location: String::from_str(&env, "Warehouse B"),
+            note: String::from_str(&env, ""),
+            metadata: Map::new(&env),
+        };
+        let reason = String::from_str(&env, "Typo in location");
+        assert_eq!(
+            tracking_client.try_amend_event(&stranger, &event_id, &fix_location, &reason),
+            Err(Ok(Error::Unauthorized))
+        );
+        assert_eq!(
+            tracking_client.try_amend_event(&carrier, &event_id, &fix_location, &String::from_str(&env, "")),
+            Err(Ok(Error::InvalidInput))
+        );
+        assert_eq!(tracking_client.amend_event(&carrier, &event_id, &fix_location, &reason), 0);
+
+        let fix_note = EventCorrection {
+            location: String::from_str(&env, ""),
+            note: String::from_str(&env, "Left dock 5"),
+            metadata: Map::new(&env),
+        };
+        assert_eq!(tracking_client.amend_event(&owner, &event_id, &fix_note, &reason), 1);
+
+        // The original event is untouched
+        assert_eq!(tracking_client.get_event(&event_id), original);
+        assert_eq!(tracking_client.verify_event_chain(&product_id), None);
+
+        let effective = tracking_client.get_effective_event(&event_id);
+        assert!(effective.amended);
+        assert_eq!(effective.amendment_count, 2);
+        assert_eq!(effective.event.location, String::from_str(&env, "Warehouse B"));
+        assert_eq!(effective.event.note, String::from_str(&env, "Left dock 5"));
+
+        let amendments = tracking_client.get_event_amendments(&event_id);
+        assert_eq!(amendments.len(), 2);
+        assert_eq!(amendments.get_unchecked(1).amended_by, owner);
+
+        let events = tracking_client.get_effective_events(&product_id, &0, &10);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events.get_unchecked(0), effective);
+    }
+
+    fn event_input(env: &Env, product_id: &String, event_type: &str, note: &str) -> TrackingEventInput {
+        TrackingEventInput {
+            product_id: product_id.clone(),
+            event_type: Symbol::new(env, event_type),
+            occurred_at: 0,
+            location: String::from_str(env, "Dock 3"),
+            position: EventPosition::None,
+            readings: Vec::new(env),
+            data_hash: BytesN::from_array(env, &[0; 32]),
+            note: String::from_str(env, note),
+            metadata: Map::new(env),
+        }
+    }
+
+    #[test]
+    fn test_every_entry_point_checks_the_actor() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_cl_client, registry_client, _admin, _cl_id, tracking_client) = setup_initialized(&env);
+        let owner = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &owner, "PROD1");
+        let input = event_input(&env, &product_id, "scanned", "");
+
+        let res = tracking_client.try_submit_tracking_event(&stranger, &input);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+        let res = tracking_client.try_add_tracking_events_batch(&stranger, &Vec::from_array(&env, [input.clone()]));
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+        registry_client.deactivate_product(&owner, &product_id, &String::from_str(&env, "Spoiled"));
+        let res = tracking_client.try_submit_tracking_event(&owner, &input);
+        assert_eq!(res, Err(Ok(Error::ProductDeactivated)));
+        assert_eq!(tracking_client.get_event_count(&product_id), 0);
+    }
+
+    #[test]
+    fn test_add_tracking_events_batch() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_cl_client, registry_client, _admin, _cl_id, tracking_client) = setup_initialized(&env);
+        let owner = Address::generate(&env);
+        let first = register_test_product(&env, &registry_client, &owner, "PROD1");
+        let second = register_test_product(&env, &registry_client, &owner, "PROD2");
+
+        let mut events = Vec::new(&env);
+        events.push_back(event_input(&env, &first, "scanned", "Inbound"));
+        events.push_back(event_input(&env, &second, "scanned", "Inbound"));
+        events.push_back(event_input(&env, &first, "shelved", "Aisle 7"));
+
+        let ids = tracking_client.add_tracking_events_batch(&owner, &events);
+        assert_eq!(ids, Vec::from_array(&env, [1u64, 2, 3]));
+        assert_eq!(tracking_client.get_product_event_ids(&first), Vec::from_array(&env, [1u64, 3]));
+        assert_eq!(tracking_client.get_event_count_by_type(&first, &Symbol::new(&env, "scanned")), 1);
+        assert_eq!(tracking_client.get_event(&3).note, String::from_str(&env, "Aisle 7"));
+        assert_eq!(tracking_client.verify_event_chain(&first), None);
+    }
+
+    #[test]
+    fn test_add_tracking_events_batch_is_atomic() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_cl_client, registry_client, _admin, _cl_id, tracking_client) = setup_initialized(&env);
+        let owner = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        let first = register_test_product(&env, &registry_client, &owner, "PROD1");
+        let second = register_test_product(&env, &registry_client, &stranger, "PROD2");
+
+        let mut too_long = event_input(&env, &first, "scanned", "");
+        too_long.note = String::from_bytes(&env, &[b'x'; 513]);
+        let mut events = Vec::new(&env);
+        events.push_back(event_input(&env, &first, "scanned", "Inbound"));
+        events.push_back(too_long);
+        assert_eq!(
+            tracking_client.try_add_tracking_events_batch(&owner, &events),
+            Err(Ok(Error::InvalidInput))
+        );
+
+        let mut events = Vec::new(&env);
+        events.push_back(event_input(&env, &first, "scanned", "Inbound"));
+        events.push_back(event_input(&env, &second, "scanned", "Inbound"));
+        assert_eq!(
+            tracking_client.try_add_tracking_events_batch(&owner, &events),
+            Err(Ok(Error::Unauthorized))
+        );
+        assert_eq!(tracking_client.get_event_count(&first), 0);
+
+        assert_eq!(
+            tracking_client.try_add_tracking_events_batch(&owner, &Vec::new(&env)),
+            Err(Ok(Error::EmptyBatch))
+        );
+        let mut events = Vec::new(&env);
+        for _ in 0..=ValidationContract::MAX_BATCH_SIZE {
+            events.push_back(event_input(&env, &first, "scanned", ""));
+        }
+        assert_eq!(
+            tracking_client.try_add_tracking_events_batch(&owner, &events),
+            Err(Ok(Error::BatchTooLarge))
+        );
+    }
+
+    #[test]
+    fn test_aggregate_event_is_shared_by_every_product() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_cl_client, registry_client, _admin, _cl_id, tracking_client) = setup_initialized(&env);
+        let owner = Address::generate(&env);
+        let mut cartons = Vec::new(&env);
+        cartons.push_back(register_test_product(&env, &registry_client, &owner, "CARTON1"));
+        cartons.push_back(register_test_product(&env, &registry_client, &owner, "CARTON2"));
+        cartons.push_back(register_test_product(&env, &registry_client, &owner, "CARTON3"));
+        let shipped = Symbol::new(&env, "shipped");
+        let location = String::from_str(&env, "Warehouse A");
+        let data_hash = BytesN::from_array(&env, &[0; 32]);
+        let note = String::from_str(&env, "Pallet 42");
+
+        // Give the second carton some history of its own first
+        let second = cartons.get_unchecked(1);
+        tracking_client.add_tracking_event(
+            &owner, &second, &Symbol::new(&env, "packed"), &location, &data_hash, &note, &Map::new(&env),
+        );
+
+        let event_id = tracking_client.add_aggregate_event(
+            &owner, &cartons, &shipped, &location, &data_hash, &note, &Map::new(&env),
+        );
+        assert_eq!(tracking_client.get_aggregate_products(&event_id), cartons);
+
+        for carton in cartons.iter() {
+            let ids = tracking_client.get_product_event_ids(&carton);
+            assert_eq!(ids.last(), Some(event_id));
+            assert_eq!(tracking_client.get_event_count_by_type(&carton, &shipped), 1);
+            assert_eq!(tracking_client.verify_event_chain(&carton), None);
+        }
+        assert_eq!(tracking_client.get_event_count(&second), 2);
+    }
+
+    #[test]
+    fn test_aggregate_event_checks_every_product() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_cl_client, registry_client, _admin, _cl_id, tracking_client) = setup_initialized(&env);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+        let mine = register_test_product(&env, &registry_client, &owner, "CARTON1");
+        let theirs = register_test_product(&env, &registry_client, &other, "CARTON2");
+        let add = |product_ids: &Vec<String>| {
+            tracking_client.try_add_aggregate_event(
+                &owner,
+                product_ids,
+                &Symbol::new(&env, "shipped"),
+                &String::from_str(&env, "Warehouse A"),
+                &BytesN::from_array(&env, &[0; 32]),
+                &String::from_str(&env, ""),
+                &Map::new(&env),
+            )
+        };
+
+        assert_eq!(add(&Vec::from_array(&env, [mine.clone(), theirs])), Err(Ok(Error::Unauthorized)));
+        assert_eq!(add(&Vec::from_array(&env, [mine.clone(), mine.clone()])), Err(Ok(Error::DuplicateProductIdInBatch)));
+        assert_eq!(add(&Vec::new(&env)), Err(Ok(Error::EmptyBatch)));
+        assert_eq!(tracking_client.get_event_count(&mine), 0);
+    }
+
+    #[test]
+    fn test_cold_chain_excursions() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_cl_client, registry_client, _admin, _cl_id, tracking_client) = setup_initialized(&env);
+        let owner = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &owner, "VACCINE1");
+        let add_reading = |at: u64, unit: SensorUnit, value_e2: i32| {
+            env.ledger().with_mut(|l| l.timestamp = at);
+            let mut input = event_input(&env, &product_id, "sensed", "");
+            input.readings = Vec::from_array(&env, [SensorReading { unit, value_e2 }]);
+            tracking_client.submit_tracking_event(&owner, &input)
+        };
+
+        let policy = ThresholdPolicy { min_temp_e2: 200, max_temp_e2: 800, max_excursion_secs: 1_800 };
+        assert_eq!(
+            tracking_client.try_set_threshold_policy(&Address::generate(&env), &product_id, &policy),
+            Err(Ok(Error::Unauthorized))
+        );
+        let inverted = ThresholdPolicy { min_temp_e2: 800, max_temp_e2: 200, max_excursion_secs: 0 };
+        assert_eq!(
+            tracking_client.try_set_threshold_policy(&owner, &product_id, &inverted),
+            Err(Ok(Error::InvalidInput))
+        );
+        tracking_client.set_threshold_policy(&owner, &product_id, &policy);
+
+        // A short spell above the range is an excursion but not a breach
+        add_reading(1_000, SensorUnit::Celsius, 500);
+        let start = add_reading(2_000, SensorUnit::Celsius, 950);
+        add_reading(2_600, SensorUnit::RelativeHumidity, 9_900);
+        add_reading(2_600, SensorUnit::Celsius, 1_100);
+        let end = add_reading(3_000, SensorUnit::Celsius, 600);
+        let excursions = tracking_client.get_excursions(&product_id);
+        assert_eq!(excursions.len(), 1);
+        let first = excursions.get_unchecked(0);
+        assert_eq!((first.start_event_id, first.end_event_id), (start, end));
+        assert_eq!(first.peak_e2, 1_100);
+        assert!(!first.open && !first.breached);
+        assert!(tracking_client.is_compliant(&product_id));
+
+        // An open excursion counts against the policy before it closes
+        add_reading(4_000, SensorUnit::Celsius, 100);
+        env.ledger().with_mut(|l| l.timestamp = 6_000);
+        assert!(!tracking_client.is_compliant(&product_id));
+
+        add_reading(6_100, SensorUnit::Celsius, 400);
+        let second = tracking_client.get_excursions(&product_id).get_unchecked(1);
+        assert!(!second.open && second.breached);
+        assert!(!tracking_client.is_compliant(&product_id));
+    }
+
+    /// Sign a device event payload with an ed25519 key.
+    fn sign_payload(env: &Env, key: &ed25519_dalek::SigningKey, payload: &Bytes) -> BytesN<64> {
+        use ed25519_dalek::Signer;
+        let mut buf = [0u8; 1024];
+        let len = payload.len() as usize;
+        payload.copy_into_slice(&mut buf[..len]);
+        BytesN::from_array(env, &key.sign(&buf[..len]).to_bytes())
+    }
+
+    #[test]
+    fn test_device_signed_events() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_cl_client, registry_client, admin, _cl_id, tracking_client) = setup_initialized(&env);
+        let org = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &org, "PROD1");
+
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+        let device = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+        let devices_id = env.register_contract(None, DeviceRegistryContract);
+        let devices_client = DeviceRegistryContractClient::new(&env, &devices_id);
+        let registration = devices_client.get_registration_payload(&org);
+        devices_client.register_device(
+            &org,
+            &device,
+            &Vec::from_array(&env, [product_id.clone()]),
+            &Vec::from_array(&env, [Symbol::new(&env, "sensed")]),
+            &sign_payload(&env, &signing_key, &registration),
+        );
+
+        let input = event_input(&env, &product_id, "sensed", "Probe 4");
+        let payload = tracking_client.get_device_event_payload(&device, &0, &input);
+        let signature = sign_payload(&env, &signing_key, &payload);
+        assert_eq!(
+            tracking_client.try_add_device_event(&device, &0, &input, &signature),
+            Err(Ok(Error::NotInitialized))
+        );
+        assert_eq!(
+            tracking_client.try_configure_devices(&org, &devices_id),
+            Err(Ok(Error::Unauthorized))
+        );
+        tracking_client.configure_devices(&admin, &devices_id);
+
+        let event_id = tracking_client.add_device_event(&device, &0, &input, &signature);
+        let event = tracking_client.get_event(&event_id);
+        assert_eq!(event.actor, org);
+        assert_eq!(event.device, EventDevice::Key(device.clone()));
+        assert_eq!(tracking_client.get_device_nonce(&device), 1);
+
+        // The same signed event can't be replayed
+        assert_eq!(
+            tracking_client.try_add_device_event(&device, &0, &input, &signature),
+            Err(Ok(Error::InvalidNonce))
+        );
+
+        let shipped = event_input(&env, &product_id, "shipped", "");
+        let payload = tracking_client.get_device_event_payload(&device, &1, &shipped);
+        let signature = sign_payload(&env, &signing_key, &payload);
+        assert_eq!(
+            tracking_client.try_add_device_event(&device, &1, &shipped, &signature),
+            Err(Ok(Error::Unauthorized))
+        );
+
+        devices_client.revoke_device(&org, &device);
+        let payload = tracking_client.get_device_event_payload(&device, &1, &input);
+        let signature = sign_payload(&env, &signing_key, &payload);
+        assert_eq!(
+            tracking_client.try_add_device_event(&device, &1, &input, &signature),
+            Err(Ok(Error::Unauthorized))
+        );
+        assert_eq!(tracking_client.get_event_count(&product_id), 1);
+    }
+
+    #[test]
+    fn test_occurred_at_windows() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (cl_client, registry_client, admin, _cl_id, tracking_client) = setup_initialized(&env);
+        let owner = Address::generate(&env);
+        let product_id = register_test_product(&env, &registry_client, &owner, "PROD1");
+        let now = 10_000_000;
+        env.ledger().with_mut(|l| l.timestamp = now);
+        let submit = |occurred_at: u64| {
+            let mut input = event_input(&env, &product_id, "harvested", "");
+            input.occurred_at = occurred_at;
+            tracking_client.try_submit_tracking_event(&owner, &input)
+        };
+
+        let event_id = submit(0).unwrap().unwrap();
+        let event = tracking_client.get_event(&event_id);
+        assert_eq!((event.occurred_at, event.recorded_at), (now, now));
+
+        // Synced hours after the fact
+        let event_id = submit(now - 6 * 60 * 60).unwrap().unwrap();
+        let event = tracking_client.get_event(&event_id);
+        assert_eq!((event.occurred_at, event.recorded_at), (now - 6 * 60 * 60, now));
+
+        let eight_days_ago = now - 8 * 24 * 60 * 60;
+        assert_eq!(submit(eight_days_ago), Err(Ok(Error::InvalidTimestamp)));
+        assert_eq!(submit(now + 10 * 60), Err(Ok(Error::InvalidTimestamp)));
+
+        cl_client.set_timestamp_windows(
+            &admin,
+            &TimestampWindows { max_backdate_secs: 30 * 24 * 60 * 60, max_forward_skew_secs: 0 },
+        );
+        assert!(submit(eight_days_ago).is_ok());
+        assert_eq!(submit(now + 1), Err(Ok(Error::InvalidTimestamp)));
     }
 }
@@ -14,10 +14,23 @@ pub struct Origin {
     pub location: String,
 }
 
+/// Identifier scheme a product ID follows. `Custom` IDs are free-form;
+/// the GS1 schemes are format-checked. SGTINs are written in element
+/// string form, `(01)<GTIN-14>(21)<serial>`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IdScheme {
+    Custom,
+    Gtin,
+    Sgtin,
+    Sscc,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProductConfig {
     pub id: String,
+    pub id_scheme: IdScheme,
     pub name: String,
     pub description: String,
     pub origin_location: String,
@@ -28,16 +41,33 @@ pub struct ProductConfig {
     pub custom: Map<Symbol, String>,
 }
 
+/// Where a product is in its life. Independent of `Product.active`, which
+/// only says whether the product is in circulation.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProductStatus {
+    Registered,
+    InProduction,
+    InTransit,
+    InStorage,
+    Delivered,
+    Sold,
+    Recalled,
+    Destroyed,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Product {
     pub id: String,
+    pub id_scheme: IdScheme,
     pub name: String,
     pub description: String,
     pub origin: Origin,
     pub owner: Address,
     pub created_at: u64,
     pub active: bool,
+    pub status: ProductStatus,
     pub category: String,
     pub tags: Vec<String>,
     pub certifications: Vec<BytesN<32>>,
@@ -46,20 +76,347 @@ pub struct Product {
     pub deactivation_info: Vec<DeactInfo>,
 }
 
+/// Editable subset of a product's fields, used by `update_product`.
+/// The ID, owner and origin of a product are fixed at registration.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProductUpdate {
+    pub name: String,
+    pub description: String,
+    pub category: String,
+    pub tags: Vec<String>,
+    pub certifications: Vec<BytesN<32>>,
+    pub media_hashes: Vec<BytesN<32>>,
+    pub custom: Map<Symbol, String>,
+}
+
+/// A superseded version of a product, archived when it was updated.
+/// Revision 0 is the product as originally registered.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProductRevision {
+    pub revision: u32,
+    pub product: Product,
+    pub replaced_at: u64,
+    pub replaced_by: Address,
+}
+
+/// A certificate attached to a product by its issuer. `id` numbers a
+/// product's certifications from 0; `expires_at` is a ledger timestamp.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Certification {
+    pub id: u32,
+    pub standard: Symbol,
+    pub issuer: Address,
+    pub document_hash: BytesN<32>,
+    pub issued_at: u64,
+    pub expires_at: u64,
+    pub revoked: bool,
+}
+
+/// A registered tracking event type. Events must carry every key in
+/// `required_metadata`; deprecated types accept no new events.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventTypeInfo {
+    pub event_type: Symbol,
+    pub description: String,
+    pub required_metadata: Vec<Symbol>,
+    pub deprecated: bool,
+    pub registered_at: u64,
+}
+
+/// A sensor or scanner that signs its own tracking events. A device is
+/// identified by its ed25519 public key and reports for its `owner`; it
+/// may only report on `products` and with `event_types` (its roles),
+/// either of which may be left empty to allow any.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Device {
+    pub public_key: BytesN<32>,
+    pub owner: Address,
+    pub products: Vec<String>,
+    pub event_types: Vec<Symbol>,
+    pub registered_at: u64,
+    pub revoked: bool,
+}
+
+/// Event types allowed to follow `from` in a product category's event
+/// sequence.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SequenceRule {
+    pub from: Symbol,
+    pub next: Vec<Symbol>,
+}
+
+/// Event ordering rules for a product category. Only event types named in
+/// `first` or the rules are sequenced; the first sequenced event must be
+/// in `first`, and each later one must be allowed by the rule for the
+/// product's last sequenced event.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventSequence {
+    pub first: Vec<Symbol>,
+    pub rules: Vec<SequenceRule>,
+}
+
+/// A product's latest event whose type its category's sequence names,
+/// kept so sequence checks need not scan the product's events.
+/// `sequence_hash` identifies the sequence the mark was taken under.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SequenceMark {
+    pub sequence_hash: BytesN<32>,
+    pub event_id: u64,
+    pub event_type: Symbol,
+}
+
+/// A partner's name for a product, such as a SKU, ERP number or RFID EPC.
+/// A value identifies at most one product within its namespace.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProductAlias {
+    pub namespace: Symbol,
+    pub value: String,
+}
+
+/// One period of a certifier's accreditation for one standard, valid over
+/// `[valid_from, valid_until)` until `suspended_at`, if set. A certifier's
+/// periods for a standard follow one another without overlapping.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Accreditation {
+    pub certifier: Address,
+    pub standard: Symbol,
+    pub valid_from: u64,
+    pub valid_until: u64,
+    pub suspended_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LifecycleAction {
+    Deactivated,
+    Reactivated,
+}
+
+/// One entry in a product's append-only lifecycle log.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LifecycleEntry {
+    pub action: LifecycleAction,
+    pub reason: String,
+    pub at: u64,
+    pub by: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LifecyclePage {
+    pub entries: Vec<LifecycleEntry>,
+    pub total_count: u64,
+    pub has_more: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecallSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+/// A recall placed on a product. Products derived from a recalled product
+/// are recalled with it; for those `origin_id` names the product the recall
+/// was issued against.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Recall {
+    pub product_id: String,
+    pub origin_id: String,
+    pub severity: RecallSeverity,
+    pub reason: String,
+    pub evidence_hash: BytesN<32>,
+    pub recalled_at: u64,
+    pub recalled_by: Address,
+}
+
+/// A WGS 84 position in degrees scaled by 10^7, with the fix's accuracy
+/// radius in metres.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GeoPoint {
+    pub lat_e7: i64,
+    pub lon_e7: i64,
+    pub accuracy_m: u32,
+}
+
+/// An event's position, if it was given one. Contract types can't hold an
+/// `Option` of a struct in this SDK, so the choice is spelled out.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EventPosition {
+    None,
+    Point(GeoPoint),
+}
+
+impl EventPosition {
+    pub fn point(&self) -> Option<GeoPoint> {
+        match self {
+            EventPosition::None => None,
+            EventPosition::Point(point) => Some(point.clone()),
+        }
+    }
+}
+
+/// A latitude/longitude box in E7 degrees. Boxes whose `min_lon_e7` is
+/// greater than `max_lon_e7` cross the antimeridian.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GeoBox {
+    pub min_lat_e7: i64,
+    pub max_lat_e7: i64,
+    pub min_lon_e7: i64,
+    pub max_lon_e7: i64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SensorUnit {
+    Celsius,
+    RelativeHumidity,
+}
+
+/// A sensor reading in hundredths of its unit, e.g. -1850 for -18.5 °C.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SensorReading {
+    pub unit: SensorUnit,
+    pub value_e2: i32,
+}
+
+/// A product's cold-chain limits, set by its owner. Temperatures are in
+/// hundredths of a degree Celsius; a product stays compliant while every
+/// spell outside the range lasts at most `max_excursion_secs`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ThresholdPolicy {
+    pub min_temp_e2: i32,
+    pub max_temp_e2: i32,
+    pub max_excursion_secs: u64,
+}
+
+/// A spell of temperature readings outside a product's policy range,
+/// from the first reading outside it to the first one back inside.
+/// `peak_e2` is the reading furthest outside the range; `breached` is set
+/// once the spell lasts longer than the policy allows.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Excursion {
+    pub product_id: String,
+    pub start_event_id: u64,
+    pub started_at: u64,
+    pub end_event_id: u64,
+    pub ended_at: u64,
+    pub open: bool,
+    pub peak_e2: i32,
+    pub breached: bool,
+}
+
+/// Limits on how far an actor-supplied `occurred_at` may lie before or
+/// after the ledger time its event is recorded at.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimestampWindows {
+    pub max_backdate_secs: u64,
+    pub max_forward_skew_secs: u64,
+}
+
+/// Which of an event's timestamps a time filter applies to.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EventTime {
+    RecordedAt,
+    OccurredAt,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TrackingEvent {
     pub event_id: u64,
     pub product_id: String,
     pub actor: Address,
-    pub timestamp: u64,
+    /// When the actor says the event happened; equal to `recorded_at`
+    /// unless supplied.
+    pub occurred_at: u64,
+    /// Ledger time the event was recorded at.
+    pub recorded_at: u64,
     pub event_type: Symbol,
     pub location: String, // Added missing location field
+    pub position: EventPosition,
+    pub readings: Vec<SensorReading>,
+    pub device: EventDevice,
     pub data_hash: BytesN<32>,
+    /// SHA-256 of the product's previous event, all zeros for its first.
+    pub prev_event_hash: BytesN<32>,
     pub note: String,
     pub metadata: Map<Symbol, String>,
 }
 
+/// Corrected values for a tracking event's location, note and metadata.
+/// Empty fields keep the original value; non-empty metadata replaces the
+/// original map.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventCorrection {
+    pub location: String,
+    pub note: String,
+    pub metadata: Map<Symbol, String>,
+}
+
+/// A correction appended to a tracking event. The event itself is never
+/// changed; `index` numbers an event's amendments from 0.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventAmendment {
+    pub event_id: u64,
+    pub index: u32,
+    pub correction: EventCorrection,
+    pub reason: String,
+    pub amended_by: Address,
+    pub amended_at: u64,
+}
+
+/// A tracking event with its amendments applied in order.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EffectiveEvent {
+    pub event: TrackingEvent,
+    pub amended: bool,
+    pub amendment_count: u32,
+}
+
+/// The device that signed an event, by public key, or `None` for events
+/// submitted by the actor directly.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EventDevice {
+    None,
+    Key(BytesN<32>),
+}
+
+impl TrackingEvent {
+    pub fn time(&self, field: EventTime) -> u64 {
+        match field {
+            EventTime::RecordedAt => self.recorded_at,
+            EventTime::OccurredAt => self.occurred_at,
+        }
+    }
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TrackingEventPage {
@@ -68,6 +425,24 @@ pub struct TrackingEventPage {
     pub has_more: bool,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProductPage {
+    pub products: Vec<Product>,
+    pub total_count: u64,
+    pub has_more: bool,
+}
+
+/// A page of the global product catalogue. `next_cursor` is the position
+/// to pass to the next `list_products` call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProductCursorPage {
+    pub products: Vec<Product>,
+    pub next_cursor: u64,
+    pub has_more: bool,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProductStats {
@@ -75,14 +450,14 @@ pub struct ProductStats {
     pub active_products: u64,
 }
 
+// Contract specs allow at most 50 variants per enum; features that need
+// many more keys get their own key enum.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
     Product(String),
     ProductEventIds(String),
-    ProductEventTimestamps(String),
-    ProductEventIdsByType(String, Symbol),
-    ProductEventIdsByActor(String, Address),
+    ProductEventHead(String),
     Event(u64),
     EventSeq,
     EventTypeIndex(String, Symbol, u64),
@@ -95,6 +470,59 @@ pub enum DataKey {
     TransferContract,
     TotalProducts,
     ActiveProducts,
+    ProductRevision(String, u32),
+    ProductRevisionCount(String),
+    OwnerProductIndex(Address, u64),
+    OwnerProductCount(Address),
+    OwnerProductPosition(String),
+    ProductIndex(u64),
+    ProductIndexCount,
+    CategoryProductIndex(String, bool, u64),
+    CategoryProductCount(String, bool),
+    CategoryProductPosition(String, bool),
+    TagProductIndex(String, bool, u64),
+    TagProductCount(String, bool),
+    TagProductPosition(String, String, bool),
+    ProductComponents(String),
+    ProductParent(String),
+    ProductSources(String),
+    DerivedProducts(String),
+    ProductRecall(String),
+    ProductLifecycle(String, u64),
+    ProductLifecycleCount(String),
+    ProductCertification(String, u32),
+    ProductCertificationCount(String),
+    StatusCount(ProductStatus),
+    StatusTransition(ProductStatus, ProductStatus),
+    EventStatus(Symbol),
+    TrackingContract,
+    CertifierRegistry,
+    EventTypeRegistry,
+    RecallIndex(u64),
+    RecallCount,
+    RecallPosition(String),
+    RegistryContract,
+    Alias(Symbol, String),
+    ProductAliases(String),
+}
+
+/// Storage keys for tracking event features added after `DataKey` filled up.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EventDataKey {
+    Sequence(String),
+    Amendment(u64, u32),
+    AmendmentCount(u64),
+    AggregateProducts(u64),
+    AggregateLink(u64, String),
+    ThresholdPolicy(String),
+    Excursion(String, u32),
+    ExcursionCount(String),
+    DeviceRegistry,
+    DeviceNonce(BytesN<32>),
+    TimestampWindows,
+    EventTypePosition(u64),
+    SequenceMark(String),
 }
 
 #[contracttype]
@@ -102,14 +530,21 @@ pub enum DataKey {
 pub struct TrackingEventInput {
     pub product_id: String,
     pub event_type: Symbol,
+    /// When the event happened, 0 for the time it is recorded.
+    pub occurred_at: u64,
+    pub location: String,
+    pub position: EventPosition,
+    pub readings: Vec<SensorReading>,
     pub data_hash: BytesN<32>,
     pub note: String,
+    pub metadata: Map<Symbol, String>,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TrackingEventFilter {
     pub event_type: Symbol,
+    pub time_field: EventTime,
     pub start_time: u64,
     pub end_time: u64,
     pub location: String,
@@ -1,7 +1,12 @@
-use soroban_sdk::{Address, Map, String, Symbol};
+use soroban_sdk::{Address, BytesN, Env, Map, String, Symbol, SymbolStr, TryFromVal, Vec};
 
 use crate::error::Error;
-use crate::types::ProductConfig;
+use crate::gs1;
+use crate::geo;
+use crate::types::{
+    EventCorrection, EventPosition, EventSequence, IdScheme, ProductConfig, ProductUpdate, SensorReading,
+    ThresholdPolicy, TimestampWindows,
+};
 
 pub struct ValidationContract;
 
@@ -16,6 +21,7 @@ impl ValidationContract {
     pub const MAX_CUSTOM_VALUE_LEN: u32 = 256;
     pub const MAX_NOTE_LEN: u32 = 512;
     pub const MAX_LOCATION_LEN: u32 = 128;
+    pub const MAX_ALIAS_LEN: u32 = 128;
 
     // --- Array / collection size limits ---
     pub const MAX_TAGS: u32 = 20;
@@ -23,6 +29,17 @@ impl ValidationContract {
     pub const MAX_MEDIA_HASHES: u32 = 50;
     pub const MAX_CUSTOM_FIELDS: u32 = 20;
     pub const MAX_METADATA_FIELDS: u32 = 20;
+    pub const MAX_BATCH_SIZE: u32 = 100;
+    pub const MAX_ALIASES: u32 = 20;
+    pub const MAX_SEQUENCE_TYPES: u32 = 50;
+    pub const MAX_AMENDMENTS: u32 = 20;
+    pub const MAX_AGGREGATE_PRODUCTS: u32 = 200;
+    pub const MAX_READINGS: u32 = 10;
+    pub const MAX_DEVICE_SCOPE: u32 = 50;
+
+    // --- Event time windows ---
+    pub const DEFAULT_MAX_BACKDATE_SECS: u64 = 7 * 24 * 60 * 60;
+    pub const DEFAULT_MAX_FORWARD_SKEW_SECS: u64 = 5 * 60;
 
     // --- Primitive validators ---
     pub fn non_empty(s: &String) -> Result<(), Error> {
@@ -52,13 +69,9 @@ impl ValidationContract {
         if config.id.len() > Self::MAX_PRODUCT_ID_LEN {
             return Err(Error::ProductIdTooLong);
         }
+        Self::validate_identifier(&config.id, config.id_scheme)?;
 
-        if config.name.len() == 0 {
-            return Err(Error::InvalidProductName);
-        }
-        if config.name.len() > Self::MAX_PRODUCT_NAME_LEN {
-            return Err(Error::ProductNameTooLong);
-        }
+        Self::validate_product_name(&config.name)?;
 
         if config.origin_location.len() == 0 {
             return Err(Error::InvalidOrigin);
@@ -67,36 +80,120 @@ impl ValidationContract {
             return Err(Error::OriginTooLong);
         }
 
-        if config.category.len() == 0 {
+        Self::validate_product_details(
+            &config.category,
+            &config.description,
+            &config.tags,
+            &config.certifications,
+            &config.media_hashes,
+            &config.custom,
+        )
+    }
+
+    pub fn validate_product_update(update: &ProductUpdate) -> Result<(), Error> {
+        Self::validate_product_name(&update.name)?;
+        Self::validate_product_details(
+            &update.category,
+            &update.description,
+            &update.tags,
+            &update.certifications,
+            &update.media_hashes,
+            &update.custom,
+        )
+    }
+
+    /// Check that a product ID is a well-formed identifier of its scheme.
+    /// The ID's length has already been checked.
+    pub fn validate_identifier(id: &String, scheme: IdScheme) -> Result<(), Error> {
+        if scheme == IdScheme::Custom {
+            return Ok(());
+        }
+        let mut buf = [0u8; Self::MAX_PRODUCT_ID_LEN as usize];
+        let bytes = &mut buf[..id.len() as usize];
+        id.copy_into_slice(bytes);
+
+        match scheme {
+            IdScheme::Custom => Ok(()),
+            IdScheme::Gtin => gs1::validate_gtin(bytes),
+            IdScheme::Sgtin => gs1::parse_sgtin(bytes).map(|_| ()),
+            IdScheme::Sscc => gs1::validate_sscc(bytes),
+        }
+    }
+
+    fn validate_product_name(name: &String) -> Result<(), Error> {
+        if name.len() == 0 {
+            return Err(Error::InvalidProductName);
+        }
+        if name.len() > Self::MAX_PRODUCT_NAME_LEN {
+            return Err(Error::ProductNameTooLong);
+        }
+        Ok(())
+    }
+
+    fn validate_product_details(
+        category: &String,
+        description: &String,
+        tags: &Vec<String>,
+        certifications: &Vec<BytesN<32>>,
+        media_hashes: &Vec<BytesN<32>>,
+        custom: &Map<Symbol, String>,
+    ) -> Result<(), Error> {
+        if category.len() == 0 {
             return Err(Error::InvalidCategory);
         }
-        if config.category.len() > Self::MAX_CATEGORY_LEN {
+        if category.len() > Self::MAX_CATEGORY_LEN {
             return Err(Error::CategoryTooLong);
         }
 
-        if config.description.len() > Self::MAX_DESCRIPTION_LEN {
+        if description.len() > Self::MAX_DESCRIPTION_LEN {
             return Err(Error::DescriptionTooLong);
         }
 
-        if config.tags.len() > Self::MAX_TAGS {
+        if tags.len() > Self::MAX_TAGS {
             return Err(Error::TooManyTags);
         }
-        for i in 0..config.tags.len() {
-            let t = config.tags.get_unchecked(i);
+        for i in 0..tags.len() {
+            let t = tags.get_unchecked(i);
             if t.len() > Self::MAX_TAG_LEN {
                 return Err(Error::TagTooLong);
             }
         }
 
-        if config.certifications.len() > Self::MAX_CERTIFICATIONS {
+        if certifications.len() > Self::MAX_CERTIFICATIONS {
             return Err(Error::TooManyCertifications);
         }
 
-        if config.media_hashes.len() > Self::MAX_MEDIA_HASHES {
+        if media_hashes.len() > Self::MAX_MEDIA_HASHES {
             return Err(Error::TooManyMediaHashes);
         }
 
-        Self::validate_custom_fields(&config.custom)
+        Self::validate_custom_fields(custom)
+    }
+
+    // --- Batch validation ---
+    pub fn validate_batch_size(len: u32) -> Result<(), Error> {
+        if len == 0 {
+            return Err(Error::EmptyBatch);
+        }
+        if len > Self::MAX_BATCH_SIZE {
+            return Err(Error::BatchTooLarge);
+        }
+        Ok(())
+    }
+
+    pub fn validate_aggregate_size(len: u32) -> Result<(), Error> {
+        if len == 0 {
+            return Err(Error::EmptyBatch);
+        }
+        if len > Self::MAX_AGGREGATE_PRODUCTS {
+            return Err(Error::BatchTooLarge);
+        }
+        Ok(())
+    }
+
+    pub fn validate_alias_value(value: &String) -> Result<(), Error> {
+        Self::non_empty(value)?;
+        Self::max_len(value, Self::MAX_ALIAS_LEN)
     }
 
     pub fn validate_deactivation_reason(reason: &String) -> Result<(), Error> {
@@ -141,6 +238,25 @@ impl ValidationContract {
         Ok(())
     }
 
+    /// A sequence may name at most `MAX_SEQUENCE_TYPES` first types, rules
+    /// and next types per rule, with one rule per `from` type.
+    pub fn validate_event_sequence(sequence: &EventSequence) -> Result<(), Error> {
+        if sequence.first.len() > Self::MAX_SEQUENCE_TYPES
+            || sequence.rules.len() > Self::MAX_SEQUENCE_TYPES
+        {
+            return Err(Error::InvalidInput);
+        }
+        for (i, rule) in sequence.rules.iter().enumerate() {
+            if rule.next.len() > Self::MAX_SEQUENCE_TYPES {
+                return Err(Error::InvalidInput);
+            }
+            if sequence.rules.iter().skip(i + 1).any(|other| other.from == rule.from) {
+                return Err(Error::InvalidInput);
+            }
+        }
+        Ok(())
+    }
+
     // --- Event data validation ---
     pub fn validate_event_location(location: &String) -> Result<(), Error> {
         if location.len() > Self::MAX_LOCATION_LEN {
@@ -155,4 +271,62 @@ impl ValidationContract {
         }
         Ok(())
     }
+
+    /// An amendment must give a reason and correct at least one field.
+    pub fn validate_event_correction(correction: &EventCorrection, reason: &String) -> Result<(), Error> {
+        Self::non_empty(reason)?;
+        Self::max_len(reason, Self::MAX_NOTE_LEN)?;
+        if correction.location.len() == 0 && correction.note.len() == 0 && correction.metadata.is_empty() {
+            return Err(Error::InvalidInput);
+        }
+        Self::validate_event_location(&correction.location)?;
+        Self::validate_event_note(&correction.note)?;
+        Self::validate_metadata(&correction.metadata)
+    }
+
+    pub fn validate_event_position(position: &EventPosition) -> Result<(), Error> {
+        if position.point().is_some_and(|point| !geo::is_valid_point(&point)) {
+            return Err(Error::InvalidGeoPoint);
+        }
+        Ok(())
+    }
+
+    pub fn validate_readings(readings: &Vec<SensorReading>) -> Result<(), Error> {
+        if readings.len() > Self::MAX_READINGS {
+            return Err(Error::InvalidInput);
+        }
+        Ok(())
+    }
+
+    pub fn validate_threshold_policy(policy: &ThresholdPolicy) -> Result<(), Error> {
+        if policy.min_temp_e2 >= policy.max_temp_e2 {
+            return Err(Error::InvalidInput);
+        }
+        Ok(())
+    }
+
+    /// An actor-supplied `occurred_at` must lie within the windows around
+    /// the ledger time `now`; 0 stands for `now` and is always valid.
+    pub fn validate_occurred_at(occurred_at: u64, now: u64, windows: &TimestampWindows) -> Result<(), Error> {
+        if occurred_at == 0 {
+            return Ok(());
+        }
+        if occurred_at < now.saturating_sub(windows.max_backdate_secs)
+            || occurred_at > now.saturating_add(windows.max_forward_skew_secs)
+        {
+            return Err(Error::InvalidTimestamp);
+        }
+        Ok(())
+    }
+
+    /// Recalled products only accept events whose type starts with `recall_`.
+    pub fn validate_event_for_recalled(env: &Env, event_type: &Symbol) -> Result<(), Error> {
+        let name = SymbolStr::try_from_val(env, &event_type.to_symbol_val())
+            .map_err(|_| Error::InvalidInput)?;
+        let name: &[u8] = name.as_ref();
+        if !name.starts_with(b"recall_") {
+            return Err(Error::ProductRecalled);
+        }
+        Ok(())
+    }
 }